@@ -0,0 +1,415 @@
+//! AND-composition of Sigma protocols under one shared challenge.
+//!
+//! The prover convinces the verifier that it knows the witnesses of several
+//! statements at once, such as `log_g(y1)` and `log_g(y2)`. Every instance
+//! runs its own three moves, but all of them answer the same challenge:
+//!
+//! 1. The prover commits in every instance and sends all the commitments.
+//! 2. The verifier answers with a single challenge `c`.
+//! 3. The prover answers `c` in every instance, and the verifier checks
+//!    every instance against it.
+//!
+//! Any protocol that implements [`SigmaProver`] and [`SigmaVerifier`] can
+//! take part; the Schnorr, DLEQ, Okamoto and OR provers and verifiers do. The
+//! non-interactive [`AndProof`] carries one challenge for all the instances
+//! and their responses, and fails to verify if any instance fails.
+//!
+//! [`AndProver`] and [`AndVerifier`] implement the Sigma traits themselves,
+//! so compositions can be nested. A nested composition enters the outer
+//! challenge with the parameters and public values of all its statements,
+//! in order, as its parameters.
+
+use std::marker::PhantomData;
+
+use sha2::Sha256;
+
+use crate::encoding::{decode_proof, encode_proof, ScalarCount};
+use crate::error::{CompositionError, DecodeError, VerifyError};
+use crate::group::Group;
+use crate::hash::{ChallengeHash, HashAlgorithm};
+use crate::params::PublicParams;
+use crate::sigma::{
+    self, check_statement_length, ProverCommitted, SigmaNonces, SigmaProver, SigmaVerifier,
+    VerifierCommitted,
+};
+use crate::transcript::Transcript;
+
+/// Label that separates AND-proof transcripts from those of other protocols.
+const PROTOCOL_LABEL: &[u8] = b"zkp-and";
+
+/// A statement for [`AndVerifier`]: the verifier of its protocol and its
+/// public values.
+pub type Statement<G> = (Box<dyn SigmaVerifier<G>>, Vec<<G as Group>::Element>);
+
+/// A statement as it enters the shared challenge: the label of its protocol,
+/// the protocol parameters and the public values.
+pub type Description<G> = (
+    &'static [u8],
+    Vec<<G as Group>::Element>,
+    Vec<<G as Group>::Element>,
+);
+
+/// A prover who knows the witnesses of several statements.
+pub struct AndProver<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    provers: Vec<Box<dyn SigmaProver<G>>>,
+    hash: PhantomData<H>,
+}
+
+/// A verifier of proofs of knowledge of the witnesses of several statements.
+pub struct AndVerifier<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    statements: Vec<Statement<G>>,
+    hash: PhantomData<H>,
+}
+
+/// A non-interactive AND-proof in compact form.
+///
+/// Only the shared challenge `c` and the responses are sent; the verifier
+/// recomputes the commitments of every instance and checks that hashing them
+/// gives back `c`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AndProof<G: Group> {
+    /// The shared challenge c.
+    pub c: G::Scalar,
+    /// The responses of every instance, concatenated in order.
+    pub s: Vec<G::Scalar>,
+    /// The hash function c was derived with.
+    pub hash: HashAlgorithm,
+}
+
+impl<G: Group> AndProof<G> {
+    /// Serializes the proof as the hash identifier byte followed by the
+    /// encodings of `c` and of every response.
+    pub fn to_bytes(&self, group: &G) -> Vec<u8> {
+        encode_proof(group, self.hash, std::iter::once(&self.c).chain(&self.s))
+    }
+
+    /// Parses a proof produced by [`AndProof::to_bytes`].
+    pub fn from_bytes(group: &G, bytes: &[u8]) -> Result<Self, DecodeError> {
+        // At least the challenge
+        let (hash, mut scalars) = decode_proof(group, bytes, ScalarCount::MultipleOf(1))?;
+        let c = scalars.remove(0);
+        Ok(AndProof {
+            c,
+            s: scalars,
+            hash,
+        })
+    }
+}
+
+impl<G: Group> AndProver<G> {
+    /// Creates a prover for the statements of `provers` that derives the
+    /// shared challenge with SHA-256.
+    ///
+    /// Fails if there are no provers or if any of them runs in a group other
+    /// than `group`.
+    pub fn new(group: G, provers: Vec<Box<dyn SigmaProver<G>>>) -> Result<Self, CompositionError> {
+        Self::with_hash(group, provers)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> AndProver<G, H> {
+    /// Like [`AndProver::new`], but derives the shared challenge with `H`.
+    pub fn with_hash(
+        group: G,
+        provers: Vec<Box<dyn SigmaProver<G>>>,
+    ) -> Result<Self, CompositionError> {
+        check_groups(&group, provers.iter().map(|p| p.group()))?;
+        Ok(AndProver {
+            group,
+            provers,
+            hash: PhantomData,
+        })
+    }
+
+    /// Step 1: commits in every instance, sending the commitments of every
+    /// instance in order.
+    pub fn commit(&self) -> ProverCommitted<'_, G> {
+        self.commit_each().1
+    }
+
+    /// Commits in every instance, returning the commitments of each instance
+    /// along with the session.
+    fn commit_each(&self) -> (Vec<Vec<G::Element>>, ProverCommitted<'_, G>) {
+        let sessions: Vec<_> = self.provers.iter().map(|p| p.commit()).collect();
+        let commitments: Vec<_> = sessions.iter().map(|s| s.commitments().to_vec()).collect();
+        let committed = ProverCommitted::new(commitments.concat(), Box::new(AndNonces(sessions)));
+        (commitments, committed)
+    }
+
+    /// Produces a non-interactive proof of knowledge of every witness, bound
+    /// to the application `context`.
+    pub fn prove(&self, context: &[u8]) -> AndProof<G> {
+        let (commitments, committed) = self.commit_each();
+        let statements: Vec<_> = self
+            .provers
+            .iter()
+            .map(|p| (p.label(), p.parameters(), p.public_values()))
+            .collect();
+        let c = challenge::<G, H>(&self.group, &statements, &commitments, context);
+        let responded = committed.receive_challenge(c).respond();
+        AndProof {
+            c: responded.challenge().clone(),
+            s: responded.responses().to_vec(),
+            hash: H::ALGORITHM,
+        }
+    }
+}
+
+/// The sessions of every instance.
+struct AndNonces<'a, G: Group>(Vec<ProverCommitted<'a, G>>);
+
+impl<G: Group> SigmaNonces<G> for AndNonces<'_, G> {
+    fn respond(self: Box<Self>, c: &G::Scalar) -> Vec<G::Scalar> {
+        self.0
+            .into_iter()
+            .flat_map(|s| {
+                s.receive_challenge(c.clone())
+                    .respond()
+                    .responses()
+                    .to_vec()
+            })
+            .collect()
+    }
+}
+
+impl<G: Group> AndVerifier<G> {
+    /// Creates a verifier for `statements` that derives the shared challenge
+    /// with SHA-256.
+    ///
+    /// Fails if there are no statements or if the verifier of any of them
+    /// runs in a group other than `group`.
+    pub fn new(group: G, statements: Vec<Statement<G>>) -> Result<Self, CompositionError> {
+        Self::with_hash(group, statements)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> AndVerifier<G, H> {
+    /// Like [`AndVerifier::new`], but derives the shared challenge with `H`.
+    pub fn with_hash(group: G, statements: Vec<Statement<G>>) -> Result<Self, CompositionError> {
+        check_groups(&group, statements.iter().map(|(v, _)| v.group()))?;
+        Ok(AndVerifier {
+            group,
+            statements,
+            hash: PhantomData,
+        })
+    }
+
+    /// Starts an interactive session by receiving the commitments of every
+    /// instance, in order.
+    pub fn receive_commitment(
+        &self,
+        commitments: &[G::Element],
+    ) -> Result<VerifierCommitted<'_, G>, VerifyError> {
+        VerifierCommitted::new(self, Vec::new(), commitments.to_vec())
+    }
+
+    /// Checks with [`sigma::verify`] that every instance is an accepting
+    /// transcript for its statement, given the commitments and the responses
+    /// of every instance in order.
+    pub fn verify(
+        &self,
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        sigma::verify(self, &[], commitments, c, s)
+    }
+
+    /// Checks a non-interactive proof of knowledge of every witness made for
+    /// the application `context`.
+    pub fn verify_proof(&self, proof: &AndProof<G>, context: &[u8]) -> Result<(), VerifyError> {
+        if proof.hash != H::ALGORITHM {
+            return Err(VerifyError::HashMismatch {
+                expected: H::ALGORITHM,
+                found: proof.hash,
+            });
+        }
+        let commitments = sigma::recompute_commitments(self, &[], &proof.c, &proof.s)?;
+        let commitments: Vec<_> = self
+            .split(&commitments, |v| v.commitment_count())
+            .into_iter()
+            .map(<[_]>::to_vec)
+            .collect();
+        let statements: Vec<_> = self
+            .statements
+            .iter()
+            .map(|(verifier, public)| (verifier.label(), verifier.parameters(), public.clone()))
+            .collect();
+        if challenge::<G, H>(&self.group, &statements, &commitments, context) != proof.c {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+
+    /// Splits the commitments or responses of all instances into those of
+    /// each instance, given the number each instance sends.
+    fn split<'a, T>(
+        &self,
+        values: &'a [T],
+        count: impl Fn(&dyn SigmaVerifier<G>) -> usize,
+    ) -> Vec<&'a [T]> {
+        split(
+            values,
+            self.statements.iter().map(|(v, _)| count(v.as_ref())),
+        )
+    }
+}
+
+impl<G: Group, H: ChallengeHash> SigmaProver<G> for AndProver<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        self.provers
+            .iter()
+            .flat_map(|p| [p.parameters(), p.public_values()].concat())
+            .collect()
+    }
+
+    fn public_values(&self) -> Vec<G::Element> {
+        Vec::new()
+    }
+
+    fn commit(&self) -> ProverCommitted<'_, G> {
+        AndProver::commit(self)
+    }
+}
+
+/// The verifier holds the statements, so the statement of the composition is
+/// empty. The commitments and responses are those of every instance in
+/// order.
+impl<G: Group, H: ChallengeHash> SigmaVerifier<G> for AndVerifier<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        self.statements
+            .iter()
+            .flat_map(|(v, public)| [v.parameters(), public.clone()].concat())
+            .collect()
+    }
+
+    fn commitment_count(&self) -> usize {
+        self.statements
+            .iter()
+            .map(|(v, _)| v.commitment_count())
+            .sum()
+    }
+
+    fn response_count(&self) -> usize {
+        self.statements
+            .iter()
+            .map(|(v, _)| v.response_count())
+            .sum()
+    }
+
+    fn check_statement(&self, public: &[G::Element]) -> Result<(), VerifyError> {
+        check_statement_length(public, 0)?;
+        for (verifier, public) in &self.statements {
+            verifier.check_statement(public)?;
+        }
+        Ok(())
+    }
+
+    fn check(
+        &self,
+        _public: &[G::Element],
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        let commitments = self.split(commitments, |v| v.commitment_count());
+        let responses = self.split(s, |v| v.response_count());
+        for (((verifier, public), t), s) in self.statements.iter().zip(commitments).zip(responses) {
+            verifier.check(public, t, c, s)?;
+        }
+        Ok(())
+    }
+
+    fn recompute_commitments(
+        &self,
+        _public: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<Vec<G::Element>, VerifyError> {
+        let responses = self.split(s, |v| v.response_count());
+        let mut commitments = Vec::new();
+        for ((verifier, public), s) in self.statements.iter().zip(responses) {
+            commitments.extend(verifier.recompute_commitments(public, c, s)?);
+        }
+        Ok(commitments)
+    }
+}
+
+/// Derives the shared Fiat-Shamir challenge for `statements`, each given as
+/// its protocol label, parameters and public values, with the commitments
+/// `commitments` of every instance.
+///
+/// The transcript binds the protocol label, the application `context`, the
+/// group and its generator, which every statement shares, the number of
+/// statements, and then for each statement in order its protocol label and
+/// its parameters, public values and commitments, each list prefixed with
+/// its length.
+pub fn challenge<G: Group, H: ChallengeHash>(
+    group: &G,
+    statements: &[Description<G>],
+    commitments: &[Vec<G::Element>],
+    context: &[u8],
+) -> G::Scalar {
+    let mut transcript = Transcript::<H>::new(PROTOCOL_LABEL);
+    transcript.append_message(b"context", context);
+    transcript.append_group(group);
+    transcript.append_message(b"n", &(statements.len() as u64).to_be_bytes());
+    for ((label, parameters, public), t) in statements.iter().zip(commitments) {
+        transcript.append_message(b"protocol", label);
+        for (list_label, list) in [(&b"base"[..], parameters), (b"y", public), (b"t", t)] {
+            transcript.append_message(b"len", &(list.len() as u64).to_be_bytes());
+            for e in list {
+                transcript.append_element(list_label, group, e);
+            }
+        }
+    }
+    transcript.challenge_scalar(b"c", group)
+}
+
+/// Splits `values` into consecutive slices of the given lengths, which must
+/// add up to the length of `values`.
+fn split<T>(mut values: &[T], lengths: impl IntoIterator<Item = usize>) -> Vec<&[T]> {
+    lengths
+        .into_iter()
+        .map(|n| {
+            let (first, rest) = values.split_at(n);
+            values = rest;
+            first
+        })
+        .collect()
+}
+
+/// Checks that there is at least one statement and that every statement is
+/// over `group`.
+fn check_groups<'a, G: Group + 'a>(
+    group: &G,
+    groups: impl ExactSizeIterator<Item = &'a G>,
+) -> Result<(), CompositionError> {
+    if groups.len() == 0 {
+        return Err(CompositionError::Empty);
+    }
+    for (index, other) in groups.enumerate() {
+        if other != group {
+            return Err(CompositionError::GroupMismatch { index });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! Recovering the secret from proofs that reuse a nonce.
+//!
+//! Two proofs made with the same nonce `r` share the commitment `t = g^r`
+//! but, for different statements or contexts, answer different challenges.
+//! They are then exactly the pair of transcripts the
+//! [knowledge extractor](crate::extractor) needs, and anyone who sees both
+//! proofs learns the secret. This module demonstrates the attack; the
+//! [`NonceReuseDetector`](crate::detector::NonceReuseDetector) guards
+//! against it.
+
+use crate::error::ExtractionError;
+use crate::extractor::extract;
+use crate::group::Group;
+use crate::schnorr::SchnorrProof;
+use crate::secret::Secret;
+
+/// Recovers the secret key behind `y` from two proofs that were made with
+/// the same nonce.
+///
+/// Both proofs must be valid for `y`, whatever their contexts. Fails with
+/// [`ExtractionError::DifferentCommitments`] if the nonces differ.
+pub fn recover_secret<G: Group>(
+    group: &G,
+    y: &G::Element,
+    first: &SchnorrProof<G>,
+    second: &SchnorrProof<G>,
+) -> Result<Secret<G::Scalar>, ExtractionError> {
+    let t1 = first.commitment(group, y);
+    let t2 = second.commitment(group, y);
+    extract(
+        group,
+        y,
+        (&t1, &first.c, &first.s),
+        (&t2, &second.c, &second.s),
+    )
+}
@@ -0,0 +1,152 @@
+//! Detection of reused nonces on the verifier side.
+//!
+//! A prover that reuses a nonce leaks its secret key (see
+//! [`attack`](crate::attack)). A verifier cannot prevent that, but it can
+//! notice: [`NonceReuseDetector`] remembers the most recent commitments seen
+//! for each public key and reports a commitment that shows up again, so the
+//! key can be revoked.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::{DetectorError, NonceReuseError, ZeroCapacityError};
+use crate::group::Group;
+use crate::schnorr::SchnorrProof;
+
+/// Remembers recent commitments per public key and flags repeated ones.
+///
+/// Keys and commitments are stored by their canonical encodings. At most
+/// `capacity` commitments are kept per key; the oldest is forgotten first.
+/// The state lives in memory and can be persisted with
+/// [`NonceReuseDetector::save`] and [`NonceReuseDetector::load`].
+#[derive(Clone, Debug)]
+pub struct NonceReuseDetector {
+    capacity: usize,
+    seen: HashMap<Vec<u8>, VecDeque<Vec<u8>>>,
+}
+
+impl NonceReuseDetector {
+    /// Creates an empty detector that remembers up to `capacity` commitments
+    /// per public key.
+    ///
+    /// Fails if `capacity` is zero, since such a detector could never flag a
+    /// reuse.
+    pub fn new(capacity: usize) -> Result<Self, ZeroCapacityError> {
+        if capacity == 0 {
+            return Err(ZeroCapacityError);
+        }
+        Ok(NonceReuseDetector {
+            capacity,
+            seen: HashMap::new(),
+        })
+    }
+
+    /// Records the commitment `t` for the public key `y`.
+    ///
+    /// Fails if `y` or `t` is not a group element, or if `t` was already
+    /// recorded for `y`; the commitment is not recorded a second time.
+    pub fn check<G: Group>(
+        &mut self,
+        group: &G,
+        y: &G::Element,
+        t: &G::Element,
+    ) -> Result<(), DetectorError> {
+        group
+            .check_element(y)
+            .map_err(DetectorError::InvalidPublicKey)?;
+        group
+            .check_element(t)
+            .map_err(DetectorError::InvalidCommitment)?;
+        self.check_encoded(group.encode_element(y), group.encode_element(t))
+            .map_err(DetectorError::NonceReuse)
+    }
+
+    /// Records the commitment of a non-interactive `proof` for the public key
+    /// `y`, like [`NonceReuseDetector::check`].
+    ///
+    /// The commitment is recomputed from the proof, so check the proof
+    /// first; an invalid proof would only record garbage.
+    pub fn check_proof<G: Group>(
+        &mut self,
+        group: &G,
+        y: &G::Element,
+        proof: &SchnorrProof<G>,
+    ) -> Result<(), DetectorError> {
+        self.check(group, y, &proof.commitment(group, y))
+    }
+
+    fn check_encoded(
+        &mut self,
+        public_key: Vec<u8>,
+        commitment: Vec<u8>,
+    ) -> Result<(), NonceReuseError> {
+        let recent = self.seen.entry(public_key.clone()).or_default();
+        if recent.contains(&commitment) {
+            return Err(NonceReuseError {
+                public_key,
+                commitment,
+            });
+        }
+        if recent.len() == self.capacity {
+            recent.pop_front();
+        }
+        recent.push_back(commitment);
+        Ok(())
+    }
+
+    /// Loads a detector saved with [`NonceReuseDetector::save`], keeping up
+    /// to `capacity` commitments per public key.
+    ///
+    /// A missing file gives an empty detector. A zero `capacity` fails with
+    /// [`io::ErrorKind::InvalidInput`].
+    pub fn load(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        let mut detector = NonceReuseDetector::new(capacity)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(detector),
+            Err(e) => return Err(e),
+        };
+        let mut rest = bytes.as_slice();
+        while !rest.is_empty() {
+            let public_key = read_field(&mut rest)?;
+            let commitment = read_field(&mut rest)?;
+            // A file written by a detector with a larger capacity may hold
+            // older commitments; they are forgotten as they are replayed
+            let _ = detector.check_encoded(public_key.to_vec(), commitment.to_vec());
+        }
+        Ok(detector)
+    }
+
+    /// Writes the remembered commitments to `path`, replacing its contents.
+    ///
+    /// Each record is a public key followed by a commitment, both prefixed
+    /// with their length as a big-endian u64, oldest commitments first.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut out = Vec::new();
+        for (public_key, recent) in &self.seen {
+            for commitment in recent {
+                for field in [public_key, commitment] {
+                    out.extend_from_slice(&(field.len() as u64).to_be_bytes());
+                    out.extend_from_slice(field);
+                }
+            }
+        }
+        fs::write(path, out)
+    }
+}
+
+/// Reads one length-prefixed field from the front of `rest`.
+fn read_field<'a>(rest: &mut &'a [u8]) -> io::Result<&'a [u8]> {
+    let truncated = || io::Error::new(io::ErrorKind::InvalidData, "truncated record");
+    let (len, tail) = rest.split_first_chunk::<8>().ok_or_else(truncated)?;
+    let len = usize::try_from(u64::from_be_bytes(*len)).map_err(|_| truncated())?;
+    if tail.len() < len {
+        return Err(truncated());
+    }
+    let (field, tail) = tail.split_at(len);
+    *rest = tail;
+    Ok(field)
+}
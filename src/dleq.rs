@@ -0,0 +1,363 @@
+//! The Chaum-Pedersen proof of discrete-log equality (DLEQ).
+//!
+//! The prover convinces the verifier that two public values share one
+//! exponent, `log_g(y) = log_h(z) = x`, without revealing `x`. It runs the
+//! Schnorr protocol for both bases at once with a single nonce and a single
+//! challenge:
+//!
+//! 1. The prover picks a random `r` and sends `a = g^r` and `b = h^r`.
+//! 2. The verifier answers with a challenge `c`.
+//! 3. The prover responds with `s = r + c*x mod q`, and the verifier checks
+//!    that `g^s = a * y^c` and `h^s = b * z^c`.
+//!
+//! As with [`schnorr`](crate::schnorr), the protocol runs interactively
+//! through the [sessions](crate::sigma), starting from [`DleqProver::commit`]
+//! and [`DleqVerifier::receive_commitment`], or
+//! non-interactively with [`DleqProver::prove`] and
+//! [`DleqVerifier::verify_proof`], which derive `c` from a Fiat-Shamir
+//! transcript.
+
+use std::marker::PhantomData;
+
+use sha2::Sha256;
+
+use crate::encoding::{decode_proof, encode_proof, ScalarCount};
+use crate::error::{DecodeError, ProverError, VerifyError};
+use crate::group::Group;
+use crate::hash::{ChallengeHash, HashAlgorithm};
+use crate::params::PublicParams;
+use crate::secret::Secret;
+use crate::sigma::{
+    self, check_statement_length, LinearNonces, ProverCommitted, SigmaProver, SigmaVerifier,
+    VerifierCommitted,
+};
+use crate::transcript::Transcript;
+
+/// Label that separates DLEQ transcripts from those of other protocols.
+const PROTOCOL_LABEL: &[u8] = b"zkp-dleq";
+
+/// A prover who knows `x` with `y = g^x` and `z = h^x`.
+pub struct DleqProver<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    h: G::Element,
+    x: Secret<G::Scalar>,
+    y: G::Element,
+    z: G::Element,
+    hash: PhantomData<H>,
+}
+
+/// A verifier of proofs that `log_g(y) = log_h(z)`.
+pub struct DleqVerifier<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    h: G::Element,
+    hash: PhantomData<H>,
+}
+
+/// A non-interactive DLEQ proof in compact form.
+///
+/// Only the challenge `c` and the response `s` are sent; the verifier
+/// recomputes the commitments as `a = g^s * y^-c` and `b = h^s * z^-c`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DleqProof<G: Group> {
+    /// The challenge c.
+    pub c: G::Scalar,
+    /// The response s = r + c*x mod q.
+    pub s: G::Scalar,
+    /// The hash function c was derived with.
+    pub hash: HashAlgorithm,
+}
+
+impl<G: Group> DleqProof<G> {
+    /// Serializes the proof as the hash identifier byte followed by the
+    /// encodings of `c` and `s`.
+    pub fn to_bytes(&self, group: &G) -> Vec<u8> {
+        encode_proof(group, self.hash, [&self.c, &self.s])
+    }
+
+    /// Parses a proof produced by [`DleqProof::to_bytes`].
+    pub fn from_bytes(group: &G, bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (hash, mut scalars) = decode_proof(group, bytes, ScalarCount::Exactly(2))?;
+        let s = scalars.pop().expect("two scalars were decoded");
+        let c = scalars.pop().expect("two scalars were decoded");
+        Ok(DleqProof { c, s, hash })
+    }
+}
+
+impl<G: Group> DleqProver<G> {
+    /// Creates a prover for the secret `x` and the second base `h`, deriving
+    /// `y = g^x` and `z = h^x`. Challenges are derived with SHA-256.
+    ///
+    /// Fails if `h` is the identity or not a group element, if `x ≡ 0 (mod q)`,
+    /// or if the group cannot exponentiate by `x` in constant time.
+    pub fn new(group: G, h: G::Element, secret: G::Scalar) -> Result<Self, ProverError> {
+        Self::with_hash(group, h, secret)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> DleqProver<G, H> {
+    /// Like [`DleqProver::new`], but derives challenges with `H`.
+    pub fn with_hash(group: G, h: G::Element, secret: G::Scalar) -> Result<Self, ProverError> {
+        if !group.supports_exp_secret() {
+            return Err(ProverError::UnsupportedGroup);
+        }
+        group.check_element(&h).map_err(ProverError::InvalidBase)?;
+        if group.scalar_is_zero(&secret) {
+            return Err(ProverError::ZeroSecret);
+        }
+        let y = group.exp_secret(&group.generator(), &secret);
+        let z = group.exp_secret(&h, &secret);
+        Ok(DleqProver {
+            group,
+            h,
+            x: Secret::new(secret),
+            y,
+            z,
+            hash: PhantomData,
+        })
+    }
+
+    /// Returns `y = g^x`.
+    pub fn y(&self) -> &G::Element {
+        &self.y
+    }
+
+    /// Returns `z = h^x`.
+    pub fn z(&self) -> &G::Element {
+        &self.z
+    }
+
+    /// Step 1: picks a random nonce `r` in [1, q-1] and commits to it with
+    /// `a = g^r` and `b = h^r`.
+    pub fn commit(&self) -> ProverCommitted<'_, G> {
+        let nonces = LinearNonces::random(&self.group, std::slice::from_ref(&self.x));
+        let r = nonces.nonces()[0].expose_secret();
+        let a = self.group.exp_secret(&self.group.generator(), r);
+        let b = self.group.exp_secret(&self.h, r);
+        ProverCommitted::new(vec![a, b], Box::new(nonces))
+    }
+
+    /// Produces a non-interactive proof that `log_g(y) = log_h(z)`, bound to
+    /// the application `context`.
+    pub fn prove(&self, context: &[u8]) -> DleqProof<G> {
+        let committed = self.commit();
+        let [a, b] = committed.commitments() else {
+            unreachable!("DLEQ commits to two elements")
+        };
+        let c = challenge::<G, H>(&self.group, &self.h, &self.y, &self.z, a, b, context);
+        let responded = committed.receive_challenge(c).respond();
+        DleqProof {
+            c: responded.challenge().clone(),
+            s: responded.responses()[0].clone(),
+            hash: H::ALGORITHM,
+        }
+    }
+}
+
+impl<G: Group> DleqVerifier<G> {
+    /// Creates a verifier for the bases `g` and `h` that derives challenges
+    /// with SHA-256.
+    ///
+    /// Fails if `h` is the identity or not a group element; with `h = 1` any
+    /// `z = 1` would pass for every `y`.
+    pub fn new(group: G, h: G::Element) -> Result<Self, VerifyError> {
+        Self::with_hash(group, h)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> DleqVerifier<G, H> {
+    /// Like [`DleqVerifier::new`], but derives challenges with `H`.
+    pub fn with_hash(group: G, h: G::Element) -> Result<Self, VerifyError> {
+        group.check_element(&h).map_err(VerifyError::InvalidBase)?;
+        Ok(DleqVerifier {
+            group,
+            h,
+            hash: PhantomData,
+        })
+    }
+
+    /// Recomputes the commitments `a = g^s * y^-c` and `b = h^s * z^-c`.
+    fn recompute(
+        &self,
+        y: &G::Element,
+        z: &G::Element,
+        c: &G::Scalar,
+        s: &G::Scalar,
+    ) -> (G::Element, G::Element) {
+        let recompute = |base: &G::Element, public: &G::Element| {
+            let base_s = self.group.exp(base, s);
+            let public_c = self.group.exp(public, c);
+            self.group.op(&base_s, &self.group.invert(&public_c))
+        };
+        (recompute(&self.group.generator(), y), recompute(&self.h, z))
+    }
+
+    /// Starts an interactive session by receiving the commitments `(a, b)`
+    /// for the statement `(y, z)`.
+    ///
+    /// Fails early if any of the elements is not a group element.
+    pub fn receive_commitment(
+        &self,
+        y: &G::Element,
+        z: &G::Element,
+        a: &G::Element,
+        b: &G::Element,
+    ) -> Result<VerifierCommitted<'_, G>, VerifyError> {
+        VerifierCommitted::new(self, vec![y.clone(), z.clone()], vec![a.clone(), b.clone()])
+    }
+
+    /// Checks the transcript `((a, b), c, s)` against the statement `(y, z)`
+    /// with [`sigma::verify`], i.e. that `g^s = a * y^c` and `h^s = b * z^c`.
+    pub fn verify(
+        &self,
+        (a, b): (&G::Element, &G::Element),
+        c: &G::Scalar,
+        s: &G::Scalar,
+        y: &G::Element,
+        z: &G::Element,
+    ) -> Result<(), VerifyError> {
+        sigma::verify(
+            self,
+            &[y.clone(), z.clone()],
+            &[a.clone(), b.clone()],
+            c,
+            std::slice::from_ref(s),
+        )
+    }
+
+    /// Checks a non-interactive proof that `log_g(y) = log_h(z)` made for
+    /// the application `context`.
+    pub fn verify_proof(
+        &self,
+        y: &G::Element,
+        z: &G::Element,
+        proof: &DleqProof<G>,
+        context: &[u8],
+    ) -> Result<(), VerifyError> {
+        if proof.hash != H::ALGORITHM {
+            return Err(VerifyError::HashMismatch {
+                expected: H::ALGORITHM,
+                found: proof.hash,
+            });
+        }
+        let public = [y.clone(), z.clone()];
+        let commitments =
+            sigma::recompute_commitments(self, &public, &proof.c, std::slice::from_ref(&proof.s))?;
+        let [a, b] = commitments.as_slice() else {
+            unreachable!("DLEQ has two commitments")
+        };
+        if challenge::<G, H>(&self.group, &self.h, y, z, a, b, context) != proof.c {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl<G: Group, H: ChallengeHash> SigmaProver<G> for DleqProver<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        vec![self.h.clone()]
+    }
+
+    fn public_values(&self) -> Vec<G::Element> {
+        vec![self.y.clone(), self.z.clone()]
+    }
+
+    fn commit(&self) -> ProverCommitted<'_, G> {
+        DleqProver::commit(self)
+    }
+}
+
+/// The statement is `[y, z]`, with the commitments `[a, b]` and one response
+/// `s`.
+impl<G: Group, H: ChallengeHash> SigmaVerifier<G> for DleqVerifier<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        vec![self.h.clone()]
+    }
+
+    fn commitment_count(&self) -> usize {
+        2
+    }
+
+    fn response_count(&self) -> usize {
+        1
+    }
+
+    fn check_statement(&self, public: &[G::Element]) -> Result<(), VerifyError> {
+        check_statement_length(public, 2)?;
+        for e in public {
+            self.group
+                .check_element(e)
+                .map_err(VerifyError::InvalidPublicKey)?;
+        }
+        Ok(())
+    }
+
+    fn check(
+        &self,
+        public: &[G::Element],
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        let g = self.group.generator();
+        for ((base, commitment), y) in [&g, &self.h].into_iter().zip(commitments).zip(public) {
+            let left = self.group.exp(base, &s[0]);
+            let right = self.group.op(commitment, &self.group.exp(y, c));
+            if left != right {
+                return Err(VerifyError::EquationFailed);
+            }
+        }
+        Ok(())
+    }
+
+    fn recompute_commitments(
+        &self,
+        public: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<Vec<G::Element>, VerifyError> {
+        let (a, b) = self.recompute(&public[0], &public[1], c, &s[0]);
+        Ok(vec![a, b])
+    }
+}
+
+/// Derives the Fiat-Shamir challenge for a proof that `log_g(y) = log_h(z)`
+/// with commitments `a` and `b`.
+///
+/// The transcript binds the protocol label, the application `context`, the
+/// group and its generator, the second base `h`, the statement `(y, z)` and
+/// both commitments.
+pub fn challenge<G: Group, H: ChallengeHash>(
+    group: &G,
+    h: &G::Element,
+    y: &G::Element,
+    z: &G::Element,
+    a: &G::Element,
+    b: &G::Element,
+    context: &[u8],
+) -> G::Scalar {
+    let mut transcript = Transcript::<H>::new(PROTOCOL_LABEL);
+    transcript.append_message(b"context", context);
+    transcript.append_group(group);
+    transcript.append_element(b"h", group, h);
+    transcript.append_element(b"y", group, y);
+    transcript.append_element(b"z", group, z);
+    transcript.append_element(b"a", group, a);
+    transcript.append_element(b"b", group, b);
+    transcript.challenge_scalar(b"c", group)
+}
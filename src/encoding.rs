@@ -0,0 +1,71 @@
+//! The wire encoding shared by every proof type.
+//!
+//! A proof is serialized as the identifier byte of its hash function
+//! followed by the fixed-width encodings of its scalars, in an order each
+//! proof type documents on its `to_bytes`.
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::error::DecodeError;
+use crate::group::Group;
+use crate::hash::HashAlgorithm;
+
+/// How many scalars an encoded proof carries.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ScalarCount {
+    /// Exactly this many.
+    Exactly(usize),
+    /// A positive multiple of this many.
+    MultipleOf(usize),
+}
+
+/// Serializes a proof as the identifier byte of `hash` followed by the
+/// fixed-width encodings of `scalars`.
+pub(crate) fn encode_proof<'a, G: Group>(
+    group: &G,
+    hash: HashAlgorithm,
+    scalars: impl IntoIterator<Item = &'a G::Scalar>,
+) -> Vec<u8>
+where
+    G::Scalar: 'a,
+{
+    let mut out = vec![hash.id()];
+    for scalar in scalars {
+        out.extend_from_slice(&group.encode_scalar(scalar));
+    }
+    out
+}
+
+/// Parses the output of [`encode_proof`], checking that it holds `count`
+/// scalars before decoding any of them.
+pub(crate) fn decode_proof<G: Group>(
+    group: &G,
+    bytes: &[u8],
+    count: ScalarCount,
+) -> Result<(HashAlgorithm, Vec<G::Scalar>), DecodeError> {
+    let (&id, scalars) = bytes.split_first().ok_or(DecodeError::WrongLength {
+        expected: 1,
+        actual: 0,
+    })?;
+    let hash = HashAlgorithm::from_id(id).ok_or(DecodeError::UnknownHash(id))?;
+    // Scalar encodings have a fixed width, so the zero scalar gives it
+    let len = group
+        .encode_scalar(&group.scalar_from_biguint(&BigUint::zero()))
+        .len();
+    let expected = match count {
+        ScalarCount::Exactly(n) => n * len,
+        ScalarCount::MultipleOf(n) => n * len * scalars.len().div_ceil(n * len).max(1),
+    };
+    if scalars.len() != expected {
+        return Err(DecodeError::WrongLength {
+            expected,
+            actual: scalars.len(),
+        });
+    }
+    let scalars = scalars
+        .chunks(len)
+        .map(|chunk| group.decode_scalar(chunk))
+        .collect::<Result<_, _>>()?;
+    Ok((hash, scalars))
+}
@@ -0,0 +1,404 @@
+//! Error types returned by this crate.
+
+use std::fmt;
+
+use crate::hash::HashAlgorithm;
+
+/// Reasons why a set of [`PublicParams`](crate::PublicParams) was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamsError {
+    /// The modulus p is not prime.
+    ModulusNotPrime,
+    /// The subgroup order q is not prime.
+    OrderNotPrime,
+    /// The subgroup order q does not divide p-1.
+    OrderDoesNotDivide,
+    /// The generator g is not in the range (1, p).
+    GeneratorOutOfRange,
+    /// The generator g does not satisfy g^q ≡ 1 (mod p).
+    GeneratorWrongOrder,
+    /// The parameters carry no FIPS 186-4 provenance to verify.
+    MissingProvenance,
+    /// p and q cannot be re-derived from the domain parameter seed and counter.
+    PrimesNotFromSeed,
+    /// g cannot be re-derived from the domain parameter seed and index.
+    GeneratorNotFromSeed,
+}
+
+impl fmt::Display for ParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ParamsError::ModulusNotPrime => "modulus p is not prime",
+            ParamsError::OrderNotPrime => "subgroup order q is not prime",
+            ParamsError::OrderDoesNotDivide => "subgroup order q does not divide p-1",
+            ParamsError::GeneratorOutOfRange => "generator g is not in the range (1, p)",
+            ParamsError::GeneratorWrongOrder => "generator g does not have order q",
+            ParamsError::MissingProvenance => "parameters have no provenance to verify",
+            ParamsError::PrimesNotFromSeed => "p and q do not match the domain parameter seed",
+            ParamsError::GeneratorNotFromSeed => "g does not match the domain parameter seed",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+/// Reasons why a prover could not be created.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverError {
+    /// The secret is a multiple of q, so x = 0 and the public key is the identity.
+    ZeroSecret,
+    /// The numbers of bases and secrets differ.
+    LengthMismatch {
+        /// The number of bases.
+        bases: usize,
+        /// The number of secrets.
+        secrets: usize,
+    },
+    /// The index of the known secret is not below the number of public keys.
+    IndexOutOfRange {
+        /// The index of the known secret.
+        index: usize,
+        /// The number of public keys.
+        len: usize,
+    },
+    /// The secret does not match the public key it is claimed for.
+    WrongSecret,
+    /// The group cannot exponentiate by a secret in constant time, as for a
+    /// modulus that is even or longer than 16384 bits.
+    UnsupportedGroup,
+    /// A base other than the generator is the identity or not a valid group
+    /// element.
+    InvalidBase(ElementError),
+    /// The statement has no bases.
+    NoBases,
+    /// A base appears more than once, so the bases are not independent.
+    DuplicateBase {
+        /// The index of the repeated base.
+        index: usize,
+    },
+}
+
+impl fmt::Display for ProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProverError::ZeroSecret => f.write_str("secret is zero modulo q"),
+            ProverError::LengthMismatch { bases, secrets } => {
+                write!(f, "{secrets} secrets given for {bases} bases")
+            }
+            ProverError::IndexOutOfRange { index, len } => {
+                write!(f, "index {index} out of range for {len} public keys")
+            }
+            ProverError::WrongSecret => f.write_str("secret does not match the public key"),
+            ProverError::UnsupportedGroup => {
+                f.write_str("group cannot exponentiate secrets in constant time")
+            }
+            ProverError::InvalidBase(e) => write!(f, "invalid base: {e}"),
+            ProverError::NoBases => f.write_str("no bases given"),
+            ProverError::DuplicateBase { index } => {
+                write!(f, "base {index} repeats an earlier base")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProverError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProverError::InvalidBase(e) => Some(e),
+            ProverError::ZeroSecret
+            | ProverError::LengthMismatch { .. }
+            | ProverError::IndexOutOfRange { .. }
+            | ProverError::WrongSecret
+            | ProverError::UnsupportedGroup
+            | ProverError::NoBases
+            | ProverError::DuplicateBase { .. } => None,
+        }
+    }
+}
+
+/// Reasons why a byte string was rejected as an encoded element or scalar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input does not have the length of an encoding.
+    WrongLength {
+        /// The length of a valid encoding.
+        expected: usize,
+        /// The length of the input.
+        actual: usize,
+    },
+    /// The input encodes an integer outside the valid range.
+    OutOfRange,
+    /// The input is not the canonical encoding of a curve point.
+    InvalidPoint,
+    /// The input encodes coordinates that do not lie on the curve.
+    NotOnCurve,
+    /// The input encodes the identity element, such as the point at infinity.
+    Identity,
+    /// The input names a hash function this crate does not know.
+    UnknownHash(u8),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::WrongLength { expected, actual } => {
+                write!(f, "expected {expected} bytes, got {actual}")
+            }
+            DecodeError::OutOfRange => f.write_str("encoded value is out of range"),
+            DecodeError::InvalidPoint => f.write_str("not a valid point encoding"),
+            DecodeError::NotOnCurve => f.write_str("point is not on the curve"),
+            DecodeError::Identity => f.write_str("element is the identity"),
+            DecodeError::UnknownHash(id) => write!(f, "unknown hash function identifier {id}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Reasons why a value was rejected as an element of the prime-order group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementError {
+    /// The value is not a valid representative, e.g. not in (0, p).
+    OutOfRange,
+    /// The value is not in the subgroup of order q.
+    NotInSubgroup,
+    /// The value is the identity, which no public key, commitment or base
+    /// takes.
+    Identity,
+}
+
+impl fmt::Display for ElementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ElementError::OutOfRange => "element is out of range",
+            ElementError::NotInSubgroup => "element is not in the subgroup of order q",
+            ElementError::Identity => "element is the identity",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for ElementError {}
+
+/// Reasons why the verifier rejected a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The public key y is not a valid group element.
+    InvalidPublicKey(ElementError),
+    /// The commitment t is not a valid group element.
+    InvalidCommitment(ElementError),
+    /// A base other than the generator is the identity or not a valid group
+    /// element.
+    InvalidBase(ElementError),
+    /// The statement has no bases.
+    NoBases,
+    /// A base appears more than once, so the bases are not independent.
+    DuplicateBase {
+        /// The index of the repeated base.
+        index: usize,
+    },
+    /// A challenge or response is not below q. Scalars must be canonical so
+    /// that a transcript cannot be altered into another accepting one.
+    NonCanonicalScalar,
+    /// The verification equation g^s = t * y^c does not hold, or for a
+    /// compact proof, the recomputed t does not hash to c.
+    EquationFailed,
+    /// The number of responses does not match the statement, such as the
+    /// number of bases or of branches.
+    ResponseCount {
+        /// The number of responses the statement calls for.
+        expected: usize,
+        /// The number of responses.
+        found: usize,
+    },
+    /// The number of commitments does not match the statement, such as the
+    /// number of branches or of composed statements.
+    CommitmentCount {
+        /// The number of commitments the statement calls for.
+        expected: usize,
+        /// The number of commitments.
+        found: usize,
+    },
+    /// A statement has the wrong number of public values for its protocol.
+    StatementLength {
+        /// The number of public values the protocol calls for.
+        expected: usize,
+        /// The number of public values.
+        found: usize,
+    },
+    /// The proof was made with a different hash function than the verifier uses.
+    HashMismatch {
+        /// The hash function of the verifier.
+        expected: HashAlgorithm,
+        /// The hash function recorded in the proof.
+        found: HashAlgorithm,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::InvalidPublicKey(e) => write!(f, "invalid public key: {e}"),
+            VerifyError::InvalidCommitment(e) => write!(f, "invalid commitment: {e}"),
+            VerifyError::InvalidBase(e) => write!(f, "invalid base: {e}"),
+            VerifyError::NoBases => f.write_str("no bases given"),
+            VerifyError::DuplicateBase { index } => {
+                write!(f, "base {index} repeats an earlier base")
+            }
+            VerifyError::NonCanonicalScalar => f.write_str("scalar is not reduced modulo q"),
+            VerifyError::EquationFailed => f.write_str("verification equation does not hold"),
+            VerifyError::ResponseCount { expected, found } => {
+                write!(f, "expected {expected} responses, found {found}")
+            }
+            VerifyError::CommitmentCount { expected, found } => {
+                write!(f, "expected {expected} commitments, found {found}")
+            }
+            VerifyError::StatementLength { expected, found } => {
+                write!(f, "expected {expected} public values, found {found}")
+            }
+            VerifyError::HashMismatch { expected, found } => {
+                write!(f, "proof uses {found}, expected {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VerifyError::InvalidPublicKey(e)
+            | VerifyError::InvalidCommitment(e)
+            | VerifyError::InvalidBase(e) => Some(e),
+            VerifyError::NoBases
+            | VerifyError::DuplicateBase { .. }
+            | VerifyError::NonCanonicalScalar
+            | VerifyError::EquationFailed
+            | VerifyError::ResponseCount { .. }
+            | VerifyError::CommitmentCount { .. }
+            | VerifyError::StatementLength { .. }
+            | VerifyError::HashMismatch { .. } => None,
+        }
+    }
+}
+
+/// Reasons why statements could not be composed into an
+/// [`AndProver`](crate::AndProver) or [`AndVerifier`](crate::AndVerifier).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompositionError {
+    /// There are no statements to compose.
+    Empty,
+    /// A statement is over a different group than the composition.
+    GroupMismatch {
+        /// The index of the statement.
+        index: usize,
+    },
+}
+
+impl fmt::Display for CompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompositionError::Empty => f.write_str("no statements to compose"),
+            CompositionError::GroupMismatch { index } => {
+                write!(f, "statement {index} is over a different group")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompositionError {}
+
+/// Reasons why the knowledge extractor could not recover a secret.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtractionError {
+    /// One of the transcripts is not accepting.
+    InvalidTranscript(VerifyError),
+    /// The transcripts do not share the same commitment t.
+    DifferentCommitments,
+    /// The transcripts answer the same challenge, so they carry no more
+    /// information than one transcript.
+    EqualChallenges,
+}
+
+impl fmt::Display for ExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractionError::InvalidTranscript(e) => write!(f, "invalid transcript: {e}"),
+            ExtractionError::DifferentCommitments => {
+                f.write_str("transcripts have different commitments")
+            }
+            ExtractionError::EqualChallenges => f.write_str("transcripts have equal challenges"),
+        }
+    }
+}
+
+impl std::error::Error for ExtractionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExtractionError::InvalidTranscript(e) => Some(e),
+            ExtractionError::DifferentCommitments | ExtractionError::EqualChallenges => None,
+        }
+    }
+}
+
+/// A commitment was seen twice for the same public key, so the prover reused
+/// a nonce and its secret key must be considered compromised.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonceReuseError {
+    /// The encoding of the affected public key y.
+    pub public_key: Vec<u8>,
+    /// The encoding of the repeated commitment t.
+    pub commitment: Vec<u8>,
+}
+
+impl fmt::Display for NonceReuseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("commitment repeated for the same public key; the key is compromised")
+    }
+}
+
+impl std::error::Error for NonceReuseError {}
+
+/// A [`NonceReuseDetector`](crate::NonceReuseDetector) was asked to remember
+/// no commitments, so it could never flag a reuse.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroCapacityError;
+
+impl fmt::Display for ZeroCapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("detector capacity must be positive")
+    }
+}
+
+impl std::error::Error for ZeroCapacityError {}
+
+/// Reasons why [`NonceReuseDetector`](crate::NonceReuseDetector) rejected a
+/// commitment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DetectorError {
+    /// The public key is not a valid group element.
+    InvalidPublicKey(ElementError),
+    /// The commitment is not a valid group element.
+    InvalidCommitment(ElementError),
+    /// The commitment was already seen for the public key.
+    NonceReuse(NonceReuseError),
+}
+
+impl fmt::Display for DetectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DetectorError::InvalidPublicKey(e) => write!(f, "invalid public key: {e}"),
+            DetectorError::InvalidCommitment(e) => write!(f, "invalid commitment: {e}"),
+            DetectorError::NonceReuse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DetectorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DetectorError::InvalidPublicKey(e) | DetectorError::InvalidCommitment(e) => Some(e),
+            DetectorError::NonceReuse(e) => Some(e),
+        }
+    }
+}
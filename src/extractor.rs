@@ -0,0 +1,92 @@
+//! The knowledge extractor for the Schnorr protocol.
+//!
+//! Special soundness says that two accepting transcripts `(t, c1, s1)` and
+//! `(t, c2, s2)` with the same commitment and different challenges determine
+//! the secret: subtracting `g^s1 = t * y^c1` and `g^s2 = t * y^c2` gives
+//! `x = (s1 - s2) / (c1 - c2) mod q`. [`extract`] performs that computation,
+//! and [`rewind`] obtains the two transcripts from a prover by running it
+//! twice from the same commitment. Any prover that convinces the verifier
+//! with good probability can be rewound like this, so it must know `x`.
+
+use rand::thread_rng;
+
+use crate::error::ExtractionError;
+use crate::group::Group;
+use crate::hash::ChallengeHash;
+use crate::schnorr::{Prover, Verifier};
+use crate::secret::Secret;
+
+/// Recovers the secret `x = log_g(y)` from two accepting transcripts that
+/// share the commitment `t` but answer different challenges.
+pub fn extract<G: Group>(
+    group: &G,
+    y: &G::Element,
+    first: (&G::Element, &G::Scalar, &G::Scalar),
+    second: (&G::Element, &G::Scalar, &G::Scalar),
+) -> Result<Secret<G::Scalar>, ExtractionError> {
+    let (t1, c1, s1) = first;
+    let (t2, c2, s2) = second;
+    let verifier = Verifier::new(group.clone());
+    verifier
+        .verify(t1, c1, s1, y)
+        .map_err(ExtractionError::InvalidTranscript)?;
+    verifier
+        .verify(t2, c2, s2, y)
+        .map_err(ExtractionError::InvalidTranscript)?;
+    if t1 != t2 {
+        return Err(ExtractionError::DifferentCommitments);
+    }
+    // Scalars are not necessarily reduced, so c and c + q must count as equal
+    let q = group.order();
+    if group.scalar_to_biguint(c1) % &q == group.scalar_to_biguint(c2) % &q {
+        return Err(ExtractionError::EqualChallenges);
+    }
+
+    let sub = |a: &G::Scalar, b: &G::Scalar| {
+        (group.scalar_to_biguint(a) + &q - group.scalar_to_biguint(b)) % &q
+    };
+    let ds = Secret::new(sub(s1, s2));
+    // q is prime, so (c1 - c2)^(q-2) = (c1 - c2)^-1 (mod q)
+    let dc_inv = sub(c1, c2).modpow(&(&q - 2u32), &q);
+    let x = Secret::new(ds.expose_secret() * dc_inv % &q);
+    Ok(Secret::new(group.scalar_from_biguint(x.expose_secret())))
+}
+
+/// Rewinds `prover` to extract its secret.
+///
+/// The prover is run twice from the same nonce, as if rewound to just after
+/// its commitment, and answers two distinct random challenges. The resulting
+/// transcripts are handed to [`extract`].
+pub fn rewind<G: Group, H: ChallengeHash>(
+    prover: &Prover<G, H>,
+) -> Result<Secret<G::Scalar>, ExtractionError> {
+    let group = prover.group();
+    let mut rng = thread_rng();
+    let c1 = group.random_scalar(&mut rng);
+    let c2 = loop {
+        let c = group.random_scalar(&mut rng);
+        if c != c1 {
+            break c;
+        }
+    };
+
+    let r = group.random_nonzero_scalar(&mut rng);
+    let committed = prover.commit_with(Secret::new(r.clone()));
+    let rewound = prover.commit_with(Secret::new(r));
+    let first = committed.receive_challenge(c1).respond();
+    let second = rewound.receive_challenge(c2).respond();
+    extract(
+        group,
+        prover.public_key(),
+        (
+            &first.commitments()[0],
+            first.challenge(),
+            &first.responses()[0],
+        ),
+        (
+            &second.commitments()[0],
+            second.challenge(),
+            &second.responses()[0],
+        ),
+    )
+}
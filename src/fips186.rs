@@ -0,0 +1,210 @@
+//! Verifiably random parameter generation following FIPS 186-4, Appendix A.
+//!
+//! p and q are derived from a random `domain_parameter_seed` with SHA-256
+//! (A.1.1.2), and g is derived from the seed, an index and p, q (A.2.3).
+//! Anyone holding the seed, counter and index can re-run the derivation with
+//! [`PublicParams::verify_provenance`] and convince themselves that the
+//! parameters were not chosen to contain a trapdoor.
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+
+use crate::error::ParamsError;
+use crate::params::{PublicParams, GENERATION_ROUNDS};
+use crate::primes::is_probable_prime;
+
+/// Output length of SHA-256 in bits.
+const OUTLEN: u64 = 256;
+
+/// The (L, N) pairs approved by FIPS 186-4, section 4.2.
+pub const APPROVED_SIZES: [(u64, u64); 4] = [(1024, 160), (2048, 224), (2048, 256), (3072, 256)];
+
+/// The values needed to re-derive FIPS 186-4 parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    /// The seed p and q were derived from.
+    pub domain_parameter_seed: Vec<u8>,
+    /// The iteration of A.1.1.2 step 11 at which p was found.
+    pub counter: u32,
+    /// The index g was derived with in A.2.3.
+    pub index: u8,
+}
+
+impl PublicParams {
+    /// Generates (L, N) parameters per FIPS 186-4 A.1.1.2 and A.2.3.
+    ///
+    /// The returned parameters carry their [`Provenance`], using generator
+    /// index 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if (L, N) is not one of the [`APPROVED_SIZES`].
+    pub fn generate_fips186<R: Rng + ?Sized>(l: u64, n: u64, rng: &mut R) -> Self {
+        assert!(
+            APPROVED_SIZES.contains(&(l, n)),
+            "(L, N) = ({l}, {n}) is not approved by FIPS 186-4"
+        );
+
+        let mut seed = vec![0u8; (n / 8) as usize];
+        loop {
+            rng.fill(&mut seed[..]);
+            let q = match derive_q(&seed, n, rng) {
+                Some(q) => q,
+                None => continue,
+            };
+            let (p, counter) = match derive_p(&seed, &q, l, None, rng) {
+                Some(found) => found,
+                None => continue,
+            };
+
+            let index = 1;
+            let g = canonical_generator(&p, &q, &seed, index)
+                .expect("a generator is found long before count wraps around");
+
+            return PublicParams {
+                p,
+                q,
+                g,
+                provenance: Some(Provenance {
+                    domain_parameter_seed: seed,
+                    counter,
+                    index,
+                }),
+            };
+        }
+    }
+
+    /// Re-derives p, q and g from the stored [`Provenance`] per FIPS 186-4
+    /// A.1.1.3 and A.2.4, and checks that they match.
+    pub fn verify_provenance(&self) -> Result<(), ParamsError> {
+        let provenance = self
+            .provenance
+            .as_ref()
+            .ok_or(ParamsError::MissingProvenance)?;
+        let seed = &provenance.domain_parameter_seed;
+        let mut rng = thread_rng();
+
+        let l = self.p.bits();
+        let n = self.q.bits();
+        if !APPROVED_SIZES.contains(&(l, n))
+            || (seed.len() as u64) * 8 < n
+            || u64::from(provenance.counter) > 4 * l - 1
+        {
+            return Err(ParamsError::PrimesNotFromSeed);
+        }
+        if derive_q(seed, n, &mut rng).as_ref() != Some(&self.q) {
+            return Err(ParamsError::PrimesNotFromSeed);
+        }
+        match derive_p(seed, &self.q, l, Some(provenance.counter), &mut rng) {
+            Some((p, counter)) if p == self.p && counter == provenance.counter => {}
+            _ => return Err(ParamsError::PrimesNotFromSeed),
+        }
+
+        if self.g < BigUint::from(2u32)
+            || self.g >= self.p
+            || !self.g.modpow(&self.q, &self.p).is_one()
+        {
+            return Err(ParamsError::GeneratorNotFromSeed);
+        }
+        let g = canonical_generator(&self.p, &self.q, seed, provenance.index);
+        if g.as_ref() != Some(&self.g) {
+            return Err(ParamsError::GeneratorNotFromSeed);
+        }
+        Ok(())
+    }
+}
+
+/// Derives a generator of the order-q subgroup from a seed and an index, per
+/// FIPS 186-4 A.2.3.
+///
+/// Different indices give independent generators whose discrete logarithms
+/// with respect to each other are unknown to everyone.
+///
+/// Returns `None` if the 16-bit counter wraps around, which for valid p and q
+/// happens with negligible probability.
+pub fn canonical_generator(
+    p: &BigUint,
+    q: &BigUint,
+    domain_parameter_seed: &[u8],
+    index: u8,
+) -> Option<BigUint> {
+    let e = (p - 1u32) / q;
+    let two = BigUint::from(2u32);
+    let mut count: u16 = 0;
+    loop {
+        count = count.checked_add(1)?;
+        let w = Sha256::new()
+            .chain_update(domain_parameter_seed)
+            .chain_update(b"ggen")
+            .chain_update([index])
+            .chain_update(count.to_be_bytes())
+            .finalize();
+        let g = BigUint::from_bytes_be(&w).modpow(&e, p);
+        if g >= two {
+            return Some(g);
+        }
+    }
+}
+
+/// A.1.1.2 steps 6-8: derives q from the seed, or `None` if it is not prime.
+fn derive_q<R: Rng + ?Sized>(seed: &[u8], n: u64, rng: &mut R) -> Option<BigUint> {
+    let top = BigUint::one() << (n - 1);
+    let u = BigUint::from_bytes_be(&Sha256::digest(seed)) % &top;
+    let q = top + &u + 1u32 - (&u % 2u32);
+    is_probable_prime(&q, GENERATION_ROUNDS, rng).then_some(q)
+}
+
+/// A.1.1.2 steps 10-11: searches for p starting from the seed, trying up to
+/// `max_counter` (default 4L - 1) candidates.
+///
+/// Returns p and the counter at which it was found.
+fn derive_p<R: Rng + ?Sized>(
+    seed: &[u8],
+    q: &BigUint,
+    l: u64,
+    max_counter: Option<u32>,
+    rng: &mut R,
+) -> Option<(BigUint, u32)> {
+    let seedlen = seed.len() * 8;
+    let seed_modulus = BigUint::one() << seedlen;
+    let seed_value = BigUint::from_bytes_be(seed);
+
+    let n = l.div_ceil(OUTLEN) - 1;
+    let b = l - 1 - n * OUTLEN;
+    let top = BigUint::one() << (l - 1);
+    let two_q: BigUint = q << 1;
+    let max_counter = max_counter.unwrap_or((4 * l - 1) as u32);
+
+    let mut offset = 1u64;
+    for counter in 0..=max_counter {
+        // W = V_0 + V_1 * 2^outlen + ... + (V_n mod 2^b) * 2^(n * outlen)
+        let mut w = BigUint::zero();
+        for j in 0..=n {
+            let input = (&seed_value + offset + j) % &seed_modulus;
+            let mut v = BigUint::from_bytes_be(&Sha256::digest(to_bytes(&input, seedlen / 8)));
+            if j == n {
+                v %= BigUint::one() << b;
+            }
+            w += v << (j * OUTLEN);
+        }
+
+        let x = w + &top;
+        let c = &x % &two_q;
+        let p = x + 1u32 - c;
+        if p >= top && is_probable_prime(&p, GENERATION_ROUNDS, rng) {
+            return Some((p, counter));
+        }
+        offset += n + 1;
+    }
+    None
+}
+
+/// Encodes `value` as a big-endian byte string of exactly `len` bytes.
+fn to_bytes(value: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = value.to_bytes_be();
+    let mut out = vec![0u8; len - bytes.len().min(len)];
+    out.extend_from_slice(&bytes[bytes.len().saturating_sub(len)..]);
+    out
+}
@@ -0,0 +1,187 @@
+//! Prime-order groups the protocols in this crate can run over.
+//!
+//! [`Group`] abstracts over the group the discrete logarithm lives in, so the
+//! Schnorr protocol can run over the order-q subgroup of Z_p* (implemented by
+//! [`PublicParams`](crate::PublicParams)) as well as over elliptic curves such
+//! as [`Ristretto255`], [`Secp256k1`] and [`P256`].
+
+use std::fmt::Debug;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::Zero;
+use rand::Rng;
+
+use crate::error::{DecodeError, ElementError};
+use crate::secret::Zeroizable;
+
+mod modp;
+mod ristretto;
+mod weierstrass;
+
+pub use ristretto::Ristretto255;
+pub use weierstrass::{Secp256k1, P256};
+
+/// A cyclic group of prime order q, written multiplicatively.
+///
+/// Scalars are integers modulo q. The scalar arithmetic has default
+/// implementations that go through [`BigUint`]; backends with a native scalar
+/// field should override them.
+///
+/// Implementations are cheap handles (unit structs or parameter sets), so the
+/// trait requires `Clone`, `Debug` and `PartialEq` to let proof types derive
+/// them.
+///
+/// No honest public key, commitment or base is the identity, since secrets
+/// and nonces are nonzero, so every backend treats it as invalid input:
+/// [`Group::decode_element`] and [`Group::check_element`] both reject it.
+pub trait Group: Clone + Debug + PartialEq {
+    /// An element of the group.
+    type Element: Clone + Debug + PartialEq;
+    /// An integer modulo the group order.
+    ///
+    /// Scalars must be [`Zeroizable`] so that secrets can be held in a
+    /// [`Secret`](crate::Secret).
+    type Scalar: Clone + Debug + PartialEq + Zeroizable;
+
+    /// Returns a byte string that identifies the group and its generator.
+    ///
+    /// Transcripts absorb it so that challenges are bound to the group.
+    fn descriptor(&self) -> Vec<u8>;
+
+    /// Returns the identity element.
+    fn identity(&self) -> Self::Element;
+
+    /// Returns the fixed generator of the group.
+    fn generator(&self) -> Self::Element;
+
+    /// Returns the (prime) order q of the group.
+    fn order(&self) -> BigUint;
+
+    /// Returns the group operation applied to `a` and `b`.
+    fn op(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Returns the inverse of `a`.
+    fn invert(&self, a: &Self::Element) -> Self::Element;
+
+    /// Returns `base` raised to the power `k`.
+    ///
+    /// This may take time that depends on `k`; use [`Group::exp_secret`] when
+    /// `k` is secret.
+    fn exp(&self, base: &Self::Element, k: &Self::Scalar) -> Self::Element;
+
+    /// Returns `base` raised to the secret power `k`, in time that does not
+    /// depend on `k`.
+    ///
+    /// The default delegates to [`Group::exp`], which is right for backends
+    /// whose exponentiation is already constant-time, such as the curves.
+    fn exp_secret(&self, base: &Self::Element, k: &Self::Scalar) -> Self::Element {
+        self.exp(base, k)
+    }
+
+    /// Returns whether [`Group::exp_secret`] runs in constant time for this
+    /// group.
+    ///
+    /// Provers refuse groups for which it does not, rather than leak their
+    /// secrets through timing.
+    fn supports_exp_secret(&self) -> bool {
+        true
+    }
+
+    /// Encodes an element as a canonical byte string.
+    fn encode_element(&self, element: &Self::Element) -> Vec<u8>;
+
+    /// Decodes an element, rejecting encodings that are not canonical and the
+    /// identity with [`DecodeError::Identity`].
+    fn decode_element(&self, bytes: &[u8]) -> Result<Self::Element, DecodeError>;
+
+    /// Checks that `element` is a member of the prime-order group other than
+    /// the identity.
+    ///
+    /// Groups whose element type can only hold members (such as prime-order
+    /// curves) keep the default, which only rejects the identity.
+    fn check_element(&self, element: &Self::Element) -> Result<(), ElementError> {
+        if element == &self.identity() {
+            return Err(ElementError::Identity);
+        }
+        Ok(())
+    }
+
+    /// Reduces an integer modulo q.
+    fn scalar_from_biguint(&self, n: &BigUint) -> Self::Scalar;
+
+    /// Returns the representative of a scalar in [0, q).
+    fn scalar_to_biguint(&self, s: &Self::Scalar) -> BigUint;
+
+    /// Encodes a scalar as a canonical byte string.
+    fn encode_scalar(&self, s: &Self::Scalar) -> Vec<u8> {
+        let len = self.order().bits().div_ceil(8) as usize;
+        let bytes = self.scalar_to_biguint(s).to_bytes_be();
+        let mut out = vec![0u8; len - bytes.len()];
+        out.extend_from_slice(&bytes);
+        out
+    }
+
+    /// Decodes a scalar, rejecting encodings of integers that are not below q.
+    fn decode_scalar(&self, bytes: &[u8]) -> Result<Self::Scalar, DecodeError> {
+        let len = self.order().bits().div_ceil(8) as usize;
+        if bytes.len() != len {
+            return Err(DecodeError::WrongLength {
+                expected: len,
+                actual: bytes.len(),
+            });
+        }
+        let n = BigUint::from_bytes_be(bytes);
+        if n >= self.order() {
+            return Err(DecodeError::OutOfRange);
+        }
+        Ok(self.scalar_from_biguint(&n))
+    }
+
+    /// Returns a uniformly random scalar in [0, q).
+    fn random_scalar<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Scalar {
+        self.scalar_from_biguint(&rng.gen_biguint_below(&self.order()))
+    }
+
+    /// Returns a uniformly random scalar in [1, q-1].
+    fn random_nonzero_scalar<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Scalar {
+        // Rejection sampling keeps the distribution uniform
+        loop {
+            let s = self.random_scalar(rng);
+            if !self.scalar_is_zero(&s) {
+                return s;
+            }
+        }
+    }
+
+    /// Returns `true` if `s` is the canonical representative of its residue,
+    /// i.e. below q.
+    ///
+    /// Verifiers reject transcripts with other scalars, since `s` and `s + q`
+    /// would otherwise both verify. Backends whose scalar type only holds
+    /// reduced values keep the default, which accepts everything.
+    fn scalar_is_canonical(&self, s: &Self::Scalar) -> bool {
+        let _ = s;
+        true
+    }
+
+    /// Returns `true` if `s` is 0 mod q.
+    fn scalar_is_zero(&self, s: &Self::Scalar) -> bool {
+        self.scalar_to_biguint(s).is_zero()
+    }
+
+    /// Returns `a + b mod q`.
+    fn scalar_add(&self, a: &Self::Scalar, b: &Self::Scalar) -> Self::Scalar {
+        self.scalar_from_biguint(&(self.scalar_to_biguint(a) + self.scalar_to_biguint(b)))
+    }
+
+    /// Returns `a * b mod q`.
+    fn scalar_mul(&self, a: &Self::Scalar, b: &Self::Scalar) -> Self::Scalar {
+        self.scalar_from_biguint(&(self.scalar_to_biguint(a) * self.scalar_to_biguint(b)))
+    }
+
+    /// Returns `-a mod q`.
+    fn scalar_neg(&self, a: &Self::Scalar) -> Self::Scalar {
+        let q = self.order();
+        self.scalar_from_biguint(&(&q - self.scalar_to_biguint(a) % &q))
+    }
+}
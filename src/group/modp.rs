@@ -0,0 +1,198 @@
+//! The order-q subgroup of Z_p*, described by [`PublicParams`].
+
+use crypto_bigint::modular::runtime_mod::{DynResidue, DynResidueParams};
+use crypto_bigint::{
+    Limb, NonZero, Uint, U1024, U16384, U2048, U256, U3072, U4096, U512, U6144, U8192,
+};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use zeroize::Zeroizing;
+
+use super::Group;
+use crate::error::{DecodeError, ElementError};
+use crate::params::PublicParams;
+
+impl PublicParams {
+    /// Length in bytes of an encoded element.
+    fn element_len(&self) -> usize {
+        self.p.bits().div_ceil(8) as usize
+    }
+}
+
+/// Elements and scalars are both [`BigUint`]s. Elements are encoded as
+/// fixed-length big-endian integers in (0, p); the identity is 1.
+impl Group for PublicParams {
+    type Element = BigUint;
+    type Scalar = BigUint;
+
+    fn descriptor(&self) -> Vec<u8> {
+        let mut out = b"modp".to_vec();
+        for n in [&self.p, &self.q, &self.g] {
+            let bytes = n.to_bytes_be();
+            out.extend_from_slice(&(bytes.len() as u64).to_be_bytes());
+            out.extend_from_slice(&bytes);
+        }
+        out
+    }
+
+    fn identity(&self) -> BigUint {
+        BigUint::one()
+    }
+
+    fn generator(&self) -> BigUint {
+        self.g.clone()
+    }
+
+    fn order(&self) -> BigUint {
+        self.q.clone()
+    }
+
+    fn op(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        (a * b) % &self.p
+    }
+
+    fn invert(&self, a: &BigUint) -> BigUint {
+        // p is prime, so a^(p-2) = a^-1 (mod p) by Fermat's little theorem
+        a.modpow(&(&self.p - 2u32), &self.p)
+    }
+
+    fn exp(&self, base: &BigUint, k: &BigUint) -> BigUint {
+        base.modpow(k, &self.p)
+    }
+
+    fn exp_secret(&self, base: &BigUint, k: &BigUint) -> BigUint {
+        // Provers reject unsupported parameters up front, so the variable-time
+        // fallback only serves direct callers
+        if !self.supports_exp_secret() {
+            return base.modpow(k, &self.p);
+        }
+        // The base is public, so reducing it in variable time is fine
+        let base = base % &self.p;
+        // No scalar this crate produces is wider than 16384 bits; a direct
+        // caller passing one gets the variable-time fallback as well
+        modpow_ct(&base, k, &self.q, &self.p).unwrap_or_else(|| base.modpow(k, &self.p))
+    }
+
+    fn supports_exp_secret(&self) -> bool {
+        // Montgomery arithmetic needs an odd modulus
+        self.p.bit(0)
+            && self.p > BigUint::one()
+            && self.p.bits() <= U16384::BITS as u64
+            && !self.q.is_zero()
+    }
+
+    fn encode_element(&self, element: &BigUint) -> Vec<u8> {
+        let bytes = element.to_bytes_be();
+        let mut out = vec![0u8; self.element_len() - bytes.len()];
+        out.extend_from_slice(&bytes);
+        out
+    }
+
+    fn decode_element(&self, bytes: &[u8]) -> Result<BigUint, DecodeError> {
+        if bytes.len() != self.element_len() {
+            return Err(DecodeError::WrongLength {
+                expected: self.element_len(),
+                actual: bytes.len(),
+            });
+        }
+        let element = BigUint::from_bytes_be(bytes);
+        if element.is_zero() || element >= self.p {
+            return Err(DecodeError::OutOfRange);
+        }
+        if element.is_one() {
+            return Err(DecodeError::Identity);
+        }
+        Ok(element)
+    }
+
+    fn check_element(&self, element: &BigUint) -> Result<(), ElementError> {
+        if element.is_zero() || element >= &self.p {
+            return Err(ElementError::OutOfRange);
+        }
+        if element.is_one() {
+            return Err(ElementError::Identity);
+        }
+        // Z_p* has elements of every order dividing p-1; only those with
+        // element^q = 1 lie in the subgroup generated by g
+        if !element.modpow(&self.q, &self.p).is_one() {
+            return Err(ElementError::NotInSubgroup);
+        }
+        Ok(())
+    }
+
+    fn scalar_is_canonical(&self, s: &BigUint) -> bool {
+        s < &self.q
+    }
+
+    fn scalar_from_biguint(&self, n: &BigUint) -> BigUint {
+        n % &self.q
+    }
+
+    fn scalar_to_biguint(&self, s: &BigUint) -> BigUint {
+        s % &self.q
+    }
+}
+
+/// Computes `base^(exponent mod order) mod modulus` with a fixed-window
+/// exponentiation whose running time depends only on the sizes of the
+/// modulus, the order and the exponent, never on the value of `exponent`.
+///
+/// The arithmetic runs on a fixed-width integer just large enough for the
+/// modulus and the exponent; the modulus must be odd and `base` below it.
+/// The exponent is reduced modulo `order` on that fixed width, so it need not
+/// be reduced beforehand. Returns `None` if either is over 16384 bits.
+fn modpow_ct(
+    base: &BigUint,
+    exponent: &BigUint,
+    order: &BigUint,
+    modulus: &BigUint,
+) -> Option<BigUint> {
+    let bits = modulus.bits().max(exponent.bits());
+    macro_rules! dispatch {
+        ($($uint:ident),*) => {
+            $(
+                if bits <= $uint::BITS as u64 {
+                    return modpow_ct_limbs::<{ $uint::LIMBS }>(base, exponent, order, modulus);
+                }
+            )*
+        };
+    }
+    dispatch!(U256, U512, U1024, U2048, U3072, U4096, U6144, U8192, U16384);
+    None
+}
+
+/// [`modpow_ct`] for a modulus and exponent that fit in `LIMBS` limbs.
+///
+/// Returns `None` if any input does not fit, or if `order` is zero.
+fn modpow_ct_limbs<const LIMBS: usize>(
+    base: &BigUint,
+    exponent: &BigUint,
+    order: &BigUint,
+    modulus: &BigUint,
+) -> Option<BigUint> {
+    let params = DynResidueParams::new(&to_uint::<LIMBS>(modulus)?);
+    let base = DynResidue::new(&to_uint::<LIMBS>(base)?, params);
+    let order = Option::<NonZero<_>>::from(NonZero::new(to_uint::<LIMBS>(order)?))?;
+    let exponent = Zeroizing::new(to_uint::<LIMBS>(exponent)?);
+    // `rem` only varies with the public divisor, so this reduction takes the
+    // same time for every exponent of the same width
+    let reduced = Zeroizing::new(exponent.rem(&order));
+    let result = base.pow_bounded_exp(&reduced, order.bits());
+    Some(from_uint(&result.retrieve()))
+}
+
+/// Converts `n` to a fixed-width integer, zeroizing the temporary encodings.
+///
+/// Returns `None` if `n` does not fit in `LIMBS` limbs.
+fn to_uint<const LIMBS: usize>(n: &BigUint) -> Option<Uint<LIMBS>> {
+    let bytes = Zeroizing::new(n.to_bytes_le());
+    let mut padded = Zeroizing::new(vec![0u8; LIMBS * Limb::BYTES]);
+    padded.get_mut(..bytes.len())?.copy_from_slice(&bytes);
+    Some(Uint::from_le_slice(&padded))
+}
+
+/// Converts a fixed-width integer back to a [`BigUint`].
+fn from_uint<const LIMBS: usize>(n: &Uint<LIMBS>) -> BigUint {
+    let bytes: Vec<u8> = n.as_words().iter().flat_map(|w| w.to_le_bytes()).collect();
+    BigUint::from_bytes_le(&bytes)
+}
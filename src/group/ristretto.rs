@@ -0,0 +1,123 @@
+//! The ristretto255 group of RFC 9496.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use num_bigint::BigUint;
+use rand::Rng;
+
+use super::Group;
+use crate::error::DecodeError;
+use crate::secret::Zeroizable;
+
+/// Length in bytes of encoded elements and scalars.
+const ENCODING_LEN: usize = 32;
+
+/// The group order l, in hexadecimal.
+const ORDER_HEX: &[u8] = b"1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+
+/// The prime-order group ristretto255, built on Curve25519.
+///
+/// Elements and scalars are encoded as 32 bytes, scalars in little-endian
+/// order as RFC 9496 specifies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ristretto255;
+
+impl Group for Ristretto255 {
+    type Element = RistrettoPoint;
+    type Scalar = Scalar;
+
+    fn descriptor(&self) -> Vec<u8> {
+        b"ristretto255".to_vec()
+    }
+
+    fn identity(&self) -> RistrettoPoint {
+        RistrettoPoint::identity()
+    }
+
+    fn generator(&self) -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_POINT
+    }
+
+    fn order(&self) -> BigUint {
+        // l = 2^252 + 27742317777372353535851937790883648493
+        BigUint::parse_bytes(ORDER_HEX, 16).expect("the group order is valid hex")
+    }
+
+    fn op(&self, a: &RistrettoPoint, b: &RistrettoPoint) -> RistrettoPoint {
+        a + b
+    }
+
+    fn invert(&self, a: &RistrettoPoint) -> RistrettoPoint {
+        -a
+    }
+
+    fn exp(&self, base: &RistrettoPoint, k: &Scalar) -> RistrettoPoint {
+        base * k
+    }
+
+    fn encode_element(&self, element: &RistrettoPoint) -> Vec<u8> {
+        element.compress().to_bytes().to_vec()
+    }
+
+    fn decode_element(&self, bytes: &[u8]) -> Result<RistrettoPoint, DecodeError> {
+        let compressed =
+            CompressedRistretto::from_slice(bytes).map_err(|_| DecodeError::WrongLength {
+                expected: ENCODING_LEN,
+                actual: bytes.len(),
+            })?;
+        let point = compressed.decompress().ok_or(DecodeError::InvalidPoint)?;
+        if point == RistrettoPoint::identity() {
+            return Err(DecodeError::Identity);
+        }
+        Ok(point)
+    }
+
+    fn scalar_from_biguint(&self, n: &BigUint) -> Scalar {
+        let mut bytes = [0u8; 64];
+        let reduced = (n % self.order()).to_bytes_le();
+        bytes[..reduced.len()].copy_from_slice(&reduced);
+        Scalar::from_bytes_mod_order_wide(&bytes)
+    }
+
+    fn scalar_to_biguint(&self, s: &Scalar) -> BigUint {
+        BigUint::from_bytes_le(s.as_bytes())
+    }
+
+    fn encode_scalar(&self, s: &Scalar) -> Vec<u8> {
+        s.to_bytes().to_vec()
+    }
+
+    fn decode_scalar(&self, bytes: &[u8]) -> Result<Scalar, DecodeError> {
+        let bytes: [u8; ENCODING_LEN] = bytes.try_into().map_err(|_| DecodeError::WrongLength {
+            expected: ENCODING_LEN,
+            actual: bytes.len(),
+        })?;
+        Option::from(Scalar::from_canonical_bytes(bytes)).ok_or(DecodeError::OutOfRange)
+    }
+
+    fn random_scalar<R: Rng + ?Sized>(&self, rng: &mut R) -> Scalar {
+        let mut wide = [0u8; 64];
+        rng.fill(&mut wide[..]);
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+
+    fn scalar_add(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a + b
+    }
+
+    fn scalar_mul(&self, a: &Scalar, b: &Scalar) -> Scalar {
+        a * b
+    }
+
+    fn scalar_neg(&self, a: &Scalar) -> Scalar {
+        -a
+    }
+}
+
+impl Zeroizable for Scalar {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
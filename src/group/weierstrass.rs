@@ -0,0 +1,156 @@
+//! Short-Weierstrass curves: secp256k1 and NIST P-256.
+//!
+//! Both curves have prime order and cofactor 1. Elements are encoded as
+//! 33-byte compressed SEC1 points and scalars as 32-byte big-endian integers.
+
+use num_bigint::BigUint;
+use rand::Rng;
+
+use super::Group;
+use crate::error::DecodeError;
+use crate::secret::Zeroizable;
+
+/// Length in bytes of a compressed SEC1 point.
+const COMPRESSED_LEN: usize = 33;
+
+/// Length in bytes of an encoded scalar.
+const SCALAR_LEN: usize = 32;
+
+/// SEC1 tag of the point at infinity.
+const TAG_IDENTITY: u8 = 0x00;
+
+/// Implements [`Group`] for a curve from one of the RustCrypto curve crates.
+macro_rules! weierstrass_group {
+    ($(#[$attr:meta])* $name:ident, $krate:ident, $descriptor:literal) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $name;
+
+        impl Group for $name {
+            type Element = $krate::ProjectivePoint;
+            type Scalar = $krate::Scalar;
+
+            fn descriptor(&self) -> Vec<u8> {
+                $descriptor.to_vec()
+            }
+
+            fn identity(&self) -> Self::Element {
+                $krate::ProjectivePoint::IDENTITY
+            }
+
+            fn generator(&self) -> Self::Element {
+                $krate::ProjectivePoint::GENERATOR
+            }
+
+            fn order(&self) -> BigUint {
+                use $krate::elliptic_curve::ff::PrimeField;
+                let hex = $krate::Scalar::MODULUS.trim_start_matches("0x");
+                BigUint::parse_bytes(hex.as_bytes(), 16).expect("the group order is valid hex")
+            }
+
+            fn op(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+                a + b
+            }
+
+            fn invert(&self, a: &Self::Element) -> Self::Element {
+                -a
+            }
+
+            fn exp(&self, base: &Self::Element, k: &Self::Scalar) -> Self::Element {
+                base * k
+            }
+
+            fn encode_element(&self, element: &Self::Element) -> Vec<u8> {
+                use $krate::elliptic_curve::sec1::ToEncodedPoint;
+                element.to_encoded_point(true).as_bytes().to_vec()
+            }
+
+            fn decode_element(&self, bytes: &[u8]) -> Result<Self::Element, DecodeError> {
+                use $krate::elliptic_curve::sec1::FromEncodedPoint;
+                if bytes.first() == Some(&TAG_IDENTITY) {
+                    return Err(DecodeError::Identity);
+                }
+                if bytes.len() != COMPRESSED_LEN {
+                    return Err(DecodeError::WrongLength {
+                        expected: COMPRESSED_LEN,
+                        actual: bytes.len(),
+                    });
+                }
+                let encoded = $krate::EncodedPoint::from_bytes(bytes)
+                    .map_err(|_| DecodeError::InvalidPoint)?;
+                if !encoded.is_compressed() {
+                    return Err(DecodeError::InvalidPoint);
+                }
+                Option::<$krate::AffinePoint>::from($krate::AffinePoint::from_encoded_point(
+                    &encoded,
+                ))
+                .map($krate::ProjectivePoint::from)
+                .ok_or(DecodeError::NotOnCurve)
+            }
+
+            fn scalar_from_biguint(&self, n: &BigUint) -> Self::Scalar {
+                let reduced = (n % self.order()).to_bytes_be();
+                let mut bytes = [0u8; SCALAR_LEN];
+                bytes[SCALAR_LEN - reduced.len()..].copy_from_slice(&reduced);
+                self.decode_scalar(&bytes)
+                    .expect("a reduced integer is a canonical scalar")
+            }
+
+            fn scalar_to_biguint(&self, s: &Self::Scalar) -> BigUint {
+                BigUint::from_bytes_be(&s.to_bytes())
+            }
+
+            fn encode_scalar(&self, s: &Self::Scalar) -> Vec<u8> {
+                s.to_bytes().to_vec()
+            }
+
+            fn decode_scalar(&self, bytes: &[u8]) -> Result<Self::Scalar, DecodeError> {
+                use $krate::elliptic_curve::ff::PrimeField;
+                let repr: [u8; SCALAR_LEN] =
+                    bytes.try_into().map_err(|_| DecodeError::WrongLength {
+                        expected: SCALAR_LEN,
+                        actual: bytes.len(),
+                    })?;
+                let repr = $krate::FieldBytes::from(repr);
+                Option::from($krate::Scalar::from_repr(repr)).ok_or(DecodeError::OutOfRange)
+            }
+
+            fn random_scalar<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Scalar {
+                use $krate::elliptic_curve::Field;
+                $krate::Scalar::random(rng)
+            }
+
+            fn scalar_add(&self, a: &Self::Scalar, b: &Self::Scalar) -> Self::Scalar {
+                a + b
+            }
+
+            fn scalar_mul(&self, a: &Self::Scalar, b: &Self::Scalar) -> Self::Scalar {
+                a * b
+            }
+
+            fn scalar_neg(&self, a: &Self::Scalar) -> Self::Scalar {
+                -a
+            }
+        }
+
+        impl Zeroizable for $krate::Scalar {
+            fn zeroize(&mut self) {
+                zeroize::Zeroize::zeroize(self);
+            }
+        }
+    };
+}
+
+weierstrass_group!(
+    /// The secp256k1 curve from SEC 2, as used by Bitcoin and Ethereum.
+    Secp256k1,
+    k256,
+    b"secp256k1"
+);
+
+weierstrass_group!(
+    /// The NIST P-256 curve (secp256r1) from FIPS 186-4.
+    P256,
+    p256,
+    b"P-256"
+);
@@ -0,0 +1,175 @@
+//! Hashing to scalars without modular bias.
+//!
+//! Reducing an n-bit digest modulo q gives a biased result unless q is much
+//! smaller than 2^n. [`hash_to_scalar`] instead expands the input to
+//! ceil((log2(q) + 128) / 8) bytes with `expand_message_xmd` from RFC 9380
+//! before reducing, which leaves a bias of at most 2^-128, as in the
+//! `hash_to_field` function of RFC 9380, section 5.
+//!
+//! The hash function used for challenges is pluggable through
+//! [`ChallengeHash`], implemented for SHA-256, SHA-512, SHA3-256 and BLAKE2b.
+
+use std::fmt;
+
+use blake2::Blake2b512;
+use num_bigint::BigUint;
+use sha2::digest::core_api::BlockSizeUser;
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Sha3_256;
+
+use crate::group::Group;
+
+/// Identifies the hash function a challenge was derived with.
+///
+/// Serialized proofs record it, so a proof made with one hash function can
+/// never be checked with another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// SHA-256 from FIPS 180-4.
+    Sha256,
+    /// SHA-512 from FIPS 180-4.
+    Sha512,
+    /// SHA3-256 from FIPS 202.
+    Sha3_256,
+    /// BLAKE2b with 512-bit output from RFC 7693.
+    Blake2b512,
+}
+
+impl HashAlgorithm {
+    /// Returns the byte identifying this algorithm in serialized proofs.
+    pub fn id(self) -> u8 {
+        match self {
+            HashAlgorithm::Sha256 => 1,
+            HashAlgorithm::Sha512 => 2,
+            HashAlgorithm::Sha3_256 => 3,
+            HashAlgorithm::Blake2b512 => 4,
+        }
+    }
+
+    /// Returns the algorithm identified by `id`, if any.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(HashAlgorithm::Sha256),
+            2 => Some(HashAlgorithm::Sha512),
+            3 => Some(HashAlgorithm::Sha3_256),
+            4 => Some(HashAlgorithm::Blake2b512),
+            _ => None,
+        }
+    }
+
+    /// Returns the name absorbed into transcripts.
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha512 => "SHA-512",
+            HashAlgorithm::Sha3_256 => "SHA3-256",
+            HashAlgorithm::Blake2b512 => "BLAKE2b-512",
+        }
+    }
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A hash function that challenges can be derived with.
+///
+/// Every prover and verifier in this crate takes the hash function it derives
+/// challenges with as the type parameter `H`, SHA-256 by default.
+pub trait ChallengeHash: Digest + BlockSizeUser + Clone {
+    /// The identifier of this hash function.
+    const ALGORITHM: HashAlgorithm;
+}
+
+impl ChallengeHash for Sha256 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha256;
+}
+
+impl ChallengeHash for Sha512 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha512;
+}
+
+impl ChallengeHash for Sha3_256 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Sha3_256;
+}
+
+impl ChallengeHash for Blake2b512 {
+    const ALGORITHM: HashAlgorithm = HashAlgorithm::Blake2b512;
+}
+
+/// Extra bits of output beyond the size of q, so the reduction is unbiased
+/// up to 2^-SECURITY_BITS.
+const SECURITY_BITS: u64 = 128;
+
+/// `expand_message_xmd` from RFC 9380, section 5.3.1.
+///
+/// Returns `len` pseudorandom bytes derived from `msg` under the domain
+/// separation tag `dst`.
+///
+/// # Panics
+///
+/// Panics if `dst` is longer than 255 bytes, `len` is longer than 65535
+/// bytes, or `len` needs more than 255 blocks of output of `H`.
+pub fn expand_message_xmd<H: Digest + BlockSizeUser>(
+    msg: &[u8],
+    dst: &[u8],
+    len: usize,
+) -> Vec<u8> {
+    let b_in_bytes = <H as Digest>::output_size();
+    let s_in_bytes = <H as BlockSizeUser>::block_size();
+    let ell = len.div_ceil(b_in_bytes);
+    assert!(dst.len() <= 255, "domain separation tag is too long");
+    assert!(len <= 65535 && ell <= 255, "requested output is too long");
+
+    let dst_len = [dst.len() as u8];
+
+    // b_0 = H(Z_pad || msg || I2OSP(len, 2) || I2OSP(0, 1) || DST_prime)
+    let b_0 = H::new()
+        .chain_update(vec![0u8; s_in_bytes])
+        .chain_update(msg)
+        .chain_update((len as u16).to_be_bytes())
+        .chain_update([0u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    // b_1 = H(b_0 || I2OSP(1, 1) || DST_prime)
+    let mut b_i = H::new()
+        .chain_update(&b_0)
+        .chain_update([1u8])
+        .chain_update(dst)
+        .chain_update(dst_len)
+        .finalize();
+
+    let mut out = Vec::with_capacity(ell * b_in_bytes);
+    out.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        // b_i = H(strxor(b_0, b_(i-1)) || I2OSP(i, 1) || DST_prime)
+        let xored: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+        b_i = H::new()
+            .chain_update(xored)
+            .chain_update([i as u8])
+            .chain_update(dst)
+            .chain_update(dst_len)
+            .finalize();
+        out.extend_from_slice(&b_i);
+    }
+    out.truncate(len);
+    out
+}
+
+/// Hashes `msg` to a scalar modulo the order of `group`, with negligible bias.
+///
+/// `dst` is the domain separation tag; different uses of this function
+/// should pass different tags.
+pub fn hash_to_scalar<G: Group, H: Digest + BlockSizeUser>(
+    group: &G,
+    msg: &[u8],
+    dst: &[u8],
+) -> G::Scalar {
+    let len = (group.order().bits() + SECURITY_BITS).div_ceil(8) as usize;
+    let uniform_bytes = expand_message_xmd::<H>(msg, dst, len);
+    group.scalar_from_biguint(&BigUint::from_bytes_be(&uniform_bytes))
+}
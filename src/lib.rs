@@ -0,0 +1,80 @@
+//! Zero-knowledge proofs of knowledge of a discrete logarithm.
+//!
+//! This crate implements the Schnorr protocol, in which a prover convinces a
+//! verifier that it knows a secret `x` such that `y = g^x mod p`, without
+//! revealing `x`.
+//!
+//! The protocol runs in three moves:
+//!
+//! 1. The prover picks a random `r` and sends the commitment `t = g^r mod p`.
+//! 2. The verifier answers with a challenge `c`.
+//! 3. The prover responds with `s = r + c*x mod q`, and the verifier checks
+//!    that `g^s = t * y^c mod p`.
+//!
+//! In the interactive mode the prover and verifier move through typestate
+//! sessions, starting with [`Prover::commit`] and
+//! [`Verifier::receive_commitment`], and the verifier samples `c` uniformly
+//! at random. In the non-interactive mode [`Prover::prove`] derives `c` from
+//! a Fiat-Shamir [`Transcript`] that binds the group, the statement, `t` and
+//! an application context, and returns a compact [`SchnorrProof`] `(c, s)`,
+//! which [`Verifier::verify_proof`] checks.
+//!
+//! The [`simulator`] and [`extractor`] modules demonstrate the two properties
+//! that make the protocol a zero-knowledge proof of knowledge: transcripts can
+//! be produced without the secret, and a prover that can answer two
+//! challenges for one commitment reveals it.
+//!
+//! The prover and verifier are generic over the [`Group`] trait. The order-q
+//! subgroup of Z_p* described by [`PublicParams`] is the default group.
+//!
+//! ```
+//! use num_bigint::BigUint;
+//! use zkp::{Prover, PublicParams, Verifier};
+//!
+//! let params = PublicParams::new();
+//! let prover = Prover::new(params.clone(), BigUint::from(6u32))?;
+//! let verifier = Verifier::new(params);
+//!
+//! let proof = prover.prove(b"example");
+//! assert_eq!(verifier.verify_proof(prover.public_key(), &proof, b"example"), Ok(()));
+//! # Ok::<(), zkp::ProverError>(())
+//! ```
+
+pub mod and_proof;
+pub mod attack;
+pub mod detector;
+pub mod dleq;
+mod encoding;
+pub mod error;
+pub mod extractor;
+pub mod fips186;
+pub mod group;
+pub mod hash;
+pub mod named_groups;
+pub mod nonce;
+pub mod okamoto;
+pub mod or_proof;
+pub mod params;
+pub mod primes;
+pub mod schnorr;
+pub mod secret;
+pub mod sigma;
+pub mod simulator;
+pub mod transcript;
+
+pub use and_proof::{AndProof, AndProver, AndVerifier};
+pub use detector::NonceReuseDetector;
+pub use dleq::{DleqProof, DleqProver, DleqVerifier};
+pub use error::{
+    CompositionError, DecodeError, DetectorError, ElementError, ExtractionError, NonceReuseError,
+    ParamsError, ProverError, VerifyError, ZeroCapacityError,
+};
+pub use fips186::Provenance;
+pub use group::Group;
+pub use hash::{ChallengeHash, HashAlgorithm};
+pub use okamoto::{OkamotoProof, OkamotoProver, OkamotoVerifier};
+pub use or_proof::{OrProof, OrProver, OrVerifier};
+pub use params::PublicParams;
+pub use schnorr::{Prover, SchnorrProof, Verifier};
+pub use secret::Secret;
+pub use transcript::Transcript;
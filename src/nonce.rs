@@ -0,0 +1,101 @@
+//! Deterministic nonce generation, following RFC 6979.
+//!
+//! A Schnorr nonce that repeats, or that an attacker can predict, reveals the
+//! secret to anyone who sees the proofs. [`rfc6979_nonce`] derives the nonce
+//! from the secret and the message with HMAC-DRBG, so it never depends on the
+//! quality of a random number generator. Passing fresh randomness as `extra`
+//! gives a hedged nonce, which stays safe as long as either the randomness or
+//! the secret is sound.
+
+use hmac::{Mac, SimpleHmac};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use zeroize::Zeroizing;
+
+use crate::group::Group;
+use crate::hash::ChallengeHash;
+use crate::secret::Secret;
+
+/// Derives a nonce in [1, q-1] from the secret `x` and the message digest
+/// `h1`, as in RFC 6979 section 3.2.
+///
+/// The DRBG state and the intermediate encodings of `x` are zeroized before
+/// returning.
+///
+/// `extra` is appended to the seed material as described in RFC 6979 section
+/// 3.6; leave it empty for the deterministic nonce, or pass fresh random
+/// bytes for a hedged one.
+pub fn rfc6979_nonce<G: Group, H: ChallengeHash>(
+    group: &G,
+    x: &G::Scalar,
+    h1: &[u8],
+    extra: &[u8],
+) -> Secret<G::Scalar> {
+    let q = group.order();
+    let qlen = q.bits();
+    let rlen = qlen.div_ceil(8) as usize;
+
+    let x = int2octets(
+        Secret::new(group.scalar_to_biguint(x)).expose_secret(),
+        rlen,
+    );
+    let h1 = bits2octets(h1, &q, rlen);
+
+    let hlen = <H as hmac::digest::Digest>::output_size();
+    let mut v = Zeroizing::new(vec![0x01; hlen]);
+    let mut k = Zeroizing::new(vec![0x00; hlen]);
+    k = hmac::<H>(&k, &[&v, &[0x00], &x, &h1, extra]);
+    v = hmac::<H>(&k, &[&v]);
+    k = hmac::<H>(&k, &[&v, &[0x01], &x, &h1, extra]);
+    v = hmac::<H>(&k, &[&v]);
+
+    loop {
+        let mut t = Zeroizing::new(Vec::with_capacity(rlen + hlen));
+        while (t.len() as u64) * 8 < qlen {
+            v = hmac::<H>(&k, &[&v]);
+            t.extend_from_slice(&v);
+        }
+        let nonce = Secret::new(bits2int(&t, qlen));
+        if !nonce.expose_secret().is_zero() && nonce.expose_secret() < &q {
+            return Secret::new(group.scalar_from_biguint(nonce.expose_secret()));
+        }
+        k = hmac::<H>(&k, &[&v, &[0x00]]);
+        v = hmac::<H>(&k, &[&v]);
+    }
+}
+
+/// Computes HMAC-H under `key` over the concatenation of `parts`.
+fn hmac<H: ChallengeHash>(key: &[u8], parts: &[&[u8]]) -> Zeroizing<Vec<u8>> {
+    let mut mac =
+        <SimpleHmac<H> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    Zeroizing::new(mac.finalize().into_bytes().to_vec())
+}
+
+/// Interprets the leftmost `qlen` bits of `bytes` as an integer.
+fn bits2int(bytes: &[u8], qlen: u64) -> BigUint {
+    let value = BigUint::from_bytes_be(bytes);
+    let blen = bytes.len() as u64 * 8;
+    if blen > qlen {
+        value >> (blen - qlen)
+    } else {
+        value
+    }
+}
+
+/// Encodes `value` as exactly `rlen` big-endian bytes.
+fn int2octets(value: &BigUint, rlen: usize) -> Zeroizing<Vec<u8>> {
+    let bytes = Zeroizing::new(value.to_bytes_be());
+    let mut out = Zeroizing::new(vec![0u8; rlen.saturating_sub(bytes.len())]);
+    out.extend_from_slice(&bytes);
+    out
+}
+
+/// Reduces the digest `h1` modulo q and encodes it as `rlen` bytes.
+fn bits2octets(h1: &[u8], q: &BigUint, rlen: usize) -> Zeroizing<Vec<u8>> {
+    let z1 = bits2int(h1, q.bits());
+    let z2 = if &z1 >= q { z1 - q } else { z1 };
+    int2octets(&z2, rlen)
+}
@@ -0,0 +1,420 @@
+//! The Okamoto proof of knowledge of a representation.
+//!
+//! Generalizes the Schnorr protocol to several bases: the prover convinces
+//! the verifier that it knows `(x1, ..., xn)` with
+//! `y = g1^x1 * ... * gn^xn`, without revealing any `xi`.
+//!
+//! 1. The prover picks random `r1, ..., rn` and sends
+//!    `t = g1^r1 * ... * gn^rn`.
+//! 2. The verifier answers with a challenge `c`.
+//! 3. The prover responds with `si = ri + c*xi mod q` for every `i`, and the
+//!    verifier checks that `g1^s1 * ... * gn^sn = t * y^c`.
+//!
+//! The bases must be independent: if anyone knew a discrete-log relation
+//! between them, a representation would no longer pin down the secrets.
+//! [`PublicParams::independent_generators`] derives such bases verifiably.
+
+use std::marker::PhantomData;
+
+use num_bigint::BigUint;
+use sha2::Sha256;
+
+use crate::encoding::{decode_proof, encode_proof, ScalarCount};
+use crate::error::{DecodeError, ProverError, VerifyError};
+use crate::fips186::canonical_generator;
+use crate::group::Group;
+use crate::hash::{ChallengeHash, HashAlgorithm};
+use crate::params::PublicParams;
+use crate::secret::Secret;
+use crate::sigma::{
+    self, check_statement_length, duplicate_base, LinearNonces, ProverCommitted, SigmaProver,
+    SigmaVerifier, VerifierCommitted,
+};
+use crate::transcript::Transcript;
+
+/// Label that separates Okamoto transcripts from those of other protocols.
+const PROTOCOL_LABEL: &[u8] = b"zkp-okamoto";
+
+/// Prefix of the seeds that independent generators are derived from.
+const GENERATOR_SEED: &[u8] = b"zkp-okamoto-generators";
+
+impl PublicParams {
+    /// Derives `n` generators of the order-q subgroup that are independent of
+    /// each other and of `g`.
+    ///
+    /// The i-th generator is [`canonical_generator`] with index 0 and the
+    /// seed `"zkp-okamoto-generators" || descriptor || i`, where `descriptor`
+    /// is [`Group::descriptor`] and `i` is a big-endian u64. Anyone can
+    /// re-derive them, and since they come out of a hash, nobody knows the
+    /// discrete logarithm of one to another.
+    pub fn independent_generators(&self, n: usize) -> Vec<BigUint> {
+        let mut seed = GENERATOR_SEED.to_vec();
+        seed.extend_from_slice(&self.descriptor());
+        let prefix = seed.len();
+        (0..n as u64)
+            .map(|i| {
+                seed.truncate(prefix);
+                seed.extend_from_slice(&i.to_be_bytes());
+                canonical_generator(&self.p, &self.q, &seed, 0)
+                    .expect("counter cannot wrap for valid parameters")
+            })
+            .collect()
+    }
+}
+
+/// A prover who knows a representation `(x1, ..., xn)` of `y` with respect
+/// to the bases `(g1, ..., gn)`.
+pub struct OkamotoProver<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    bases: Vec<G::Element>,
+    x: Vec<Secret<G::Scalar>>,
+    y: G::Element,
+    hash: PhantomData<H>,
+}
+
+/// A verifier of proofs of knowledge of a representation with respect to
+/// fixed bases.
+pub struct OkamotoVerifier<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    bases: Vec<G::Element>,
+    hash: PhantomData<H>,
+}
+
+/// A non-interactive Okamoto proof in compact form.
+///
+/// Only the challenge `c` and the responses `si` are sent; the verifier
+/// recomputes the commitment as `t = g1^s1 * ... * gn^sn * y^-c`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OkamotoProof<G: Group> {
+    /// The challenge c.
+    pub c: G::Scalar,
+    /// The responses si = ri + c*xi mod q, one per base.
+    pub s: Vec<G::Scalar>,
+    /// The hash function c was derived with.
+    pub hash: HashAlgorithm,
+}
+
+impl<G: Group> OkamotoProof<G> {
+    /// Serializes the proof as the hash identifier byte followed by the
+    /// encodings of `c` and of every `si`.
+    pub fn to_bytes(&self, group: &G) -> Vec<u8> {
+        encode_proof(group, self.hash, std::iter::once(&self.c).chain(&self.s))
+    }
+
+    /// Parses a proof produced by [`OkamotoProof::to_bytes`].
+    pub fn from_bytes(group: &G, bytes: &[u8]) -> Result<Self, DecodeError> {
+        // At least the challenge
+        let (hash, mut scalars) = decode_proof(group, bytes, ScalarCount::MultipleOf(1))?;
+        let c = scalars.remove(0);
+        Ok(OkamotoProof {
+            c,
+            s: scalars,
+            hash,
+        })
+    }
+}
+
+impl<G: Group> OkamotoProver<G> {
+    /// Creates a prover for the representation `secrets` with respect to
+    /// `bases`, deriving `y = g1^x1 * ... * gn^xn`. Challenges are derived
+    /// with SHA-256.
+    ///
+    /// Fails if the group cannot exponentiate by secrets in constant time, if
+    /// there are no bases, if the numbers of bases and secrets differ, if a
+    /// base is the identity, not a group element or repeated, or if every
+    /// secret is zero, which would make `y` the identity.
+    pub fn new(
+        group: G,
+        bases: Vec<G::Element>,
+        secrets: Vec<G::Scalar>,
+    ) -> Result<Self, ProverError> {
+        Self::with_hash(group, bases, secrets)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> OkamotoProver<G, H> {
+    /// Like [`OkamotoProver::new`], but derives challenges with `H`.
+    pub fn with_hash(
+        group: G,
+        bases: Vec<G::Element>,
+        secrets: Vec<G::Scalar>,
+    ) -> Result<Self, ProverError> {
+        if !group.supports_exp_secret() {
+            return Err(ProverError::UnsupportedGroup);
+        }
+        if bases.is_empty() {
+            return Err(ProverError::NoBases);
+        }
+        if bases.len() != secrets.len() {
+            return Err(ProverError::LengthMismatch {
+                bases: bases.len(),
+                secrets: secrets.len(),
+            });
+        }
+        for base in &bases {
+            group
+                .check_element(base)
+                .map_err(ProverError::InvalidBase)?;
+        }
+        if let Some(index) = duplicate_base(&bases) {
+            return Err(ProverError::DuplicateBase { index });
+        }
+        let x: Vec<_> = secrets.into_iter().map(Secret::new).collect();
+        if x.iter().all(|x| group.scalar_is_zero(x.expose_secret())) {
+            return Err(ProverError::ZeroSecret);
+        }
+        let y = multi_exp_secret(&group, &bases, &x);
+        Ok(OkamotoProver {
+            group,
+            bases,
+            x,
+            y,
+            hash: PhantomData,
+        })
+    }
+
+    /// Returns the public value `y = g1^x1 * ... * gn^xn`.
+    pub fn public_key(&self) -> &G::Element {
+        &self.y
+    }
+
+    /// Step 1: picks random nonces `ri` in [1, q-1] and commits to them with
+    /// `t = g1^r1 * ... * gn^rn`.
+    pub fn commit(&self) -> ProverCommitted<'_, G> {
+        let nonces = LinearNonces::random(&self.group, &self.x);
+        let t = multi_exp_secret(&self.group, &self.bases, nonces.nonces());
+        ProverCommitted::new(vec![t], Box::new(nonces))
+    }
+
+    /// Produces a non-interactive proof of knowledge of the representation,
+    /// bound to the application `context`.
+    pub fn prove(&self, context: &[u8]) -> OkamotoProof<G> {
+        let committed = self.commit();
+        let c = challenge::<G, H>(
+            &self.group,
+            &self.bases,
+            &self.y,
+            &committed.commitments()[0],
+            context,
+        );
+        let responded = committed.receive_challenge(c).respond();
+        OkamotoProof {
+            c: responded.challenge().clone(),
+            s: responded.responses().to_vec(),
+            hash: H::ALGORITHM,
+        }
+    }
+}
+
+impl<G: Group> OkamotoVerifier<G> {
+    /// Creates a verifier for `bases` that derives challenges with SHA-256.
+    ///
+    /// Fails if there are no bases, or if a base is the identity, not a group
+    /// element or repeated.
+    pub fn new(group: G, bases: Vec<G::Element>) -> Result<Self, VerifyError> {
+        Self::with_hash(group, bases)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> OkamotoVerifier<G, H> {
+    /// Like [`OkamotoVerifier::new`], but derives challenges with `H`.
+    pub fn with_hash(group: G, bases: Vec<G::Element>) -> Result<Self, VerifyError> {
+        if bases.is_empty() {
+            return Err(VerifyError::NoBases);
+        }
+        for base in &bases {
+            group
+                .check_element(base)
+                .map_err(VerifyError::InvalidBase)?;
+        }
+        if let Some(index) = duplicate_base(&bases) {
+            return Err(VerifyError::DuplicateBase { index });
+        }
+        Ok(OkamotoVerifier {
+            group,
+            bases,
+            hash: PhantomData,
+        })
+    }
+
+    /// Recomputes the commitment `t = g1^s1 * ... * gn^sn * y^-c`.
+    fn recompute(&self, y: &G::Element, c: &G::Scalar, s: &[G::Scalar]) -> G::Element {
+        let prod = multi_exp(&self.group, &self.bases, s);
+        let y_c = self.group.exp(y, c);
+        self.group.op(&prod, &self.group.invert(&y_c))
+    }
+
+    /// Starts an interactive session by receiving the commitment `t` for the
+    /// public value `y`.
+    ///
+    /// Fails early if any of the elements is not a group element.
+    pub fn receive_commitment(
+        &self,
+        y: &G::Element,
+        t: &G::Element,
+    ) -> Result<VerifierCommitted<'_, G>, VerifyError> {
+        VerifierCommitted::new(self, vec![y.clone()], vec![t.clone()])
+    }
+
+    /// Checks the transcript `(t, c, s)` against the public value `y` with
+    /// [`sigma::verify`], i.e. that `g1^s1 * ... * gn^sn = t * y^c`.
+    pub fn verify(
+        &self,
+        t: &G::Element,
+        c: &G::Scalar,
+        s: &[G::Scalar],
+        y: &G::Element,
+    ) -> Result<(), VerifyError> {
+        sigma::verify(self, std::slice::from_ref(y), std::slice::from_ref(t), c, s)
+    }
+
+    /// Checks a non-interactive proof of knowledge of a representation of
+    /// `y` made for the application `context`.
+    pub fn verify_proof(
+        &self,
+        y: &G::Element,
+        proof: &OkamotoProof<G>,
+        context: &[u8],
+    ) -> Result<(), VerifyError> {
+        if proof.hash != H::ALGORITHM {
+            return Err(VerifyError::HashMismatch {
+                expected: H::ALGORITHM,
+                found: proof.hash,
+            });
+        }
+        let t = sigma::recompute_commitments(self, std::slice::from_ref(y), &proof.c, &proof.s)?;
+        let t = &t[0];
+        if challenge::<G, H>(&self.group, &self.bases, y, t, context) != proof.c {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl<G: Group, H: ChallengeHash> SigmaProver<G> for OkamotoProver<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        self.bases.clone()
+    }
+
+    fn public_values(&self) -> Vec<G::Element> {
+        vec![self.y.clone()]
+    }
+
+    fn commit(&self) -> ProverCommitted<'_, G> {
+        OkamotoProver::commit(self)
+    }
+}
+
+/// The statement is `[y]`, with one commitment `t` and one response per
+/// base.
+impl<G: Group, H: ChallengeHash> SigmaVerifier<G> for OkamotoVerifier<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        self.bases.clone()
+    }
+
+    fn commitment_count(&self) -> usize {
+        1
+    }
+
+    fn response_count(&self) -> usize {
+        self.bases.len()
+    }
+
+    fn check_statement(&self, public: &[G::Element]) -> Result<(), VerifyError> {
+        check_statement_length(public, 1)?;
+        self.group
+            .check_element(&public[0])
+            .map_err(VerifyError::InvalidPublicKey)
+    }
+
+    fn check(
+        &self,
+        public: &[G::Element],
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        let left = multi_exp(&self.group, &self.bases, s);
+        let right = self
+            .group
+            .op(&commitments[0], &self.group.exp(&public[0], c));
+        if left != right {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+
+    fn recompute_commitments(
+        &self,
+        public: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<Vec<G::Element>, VerifyError> {
+        Ok(vec![self.recompute(&public[0], c, s)])
+    }
+}
+
+/// Derives the Fiat-Shamir challenge for a proof of knowledge of a
+/// representation of `y` with respect to `bases`, with commitment `t`.
+///
+/// The transcript binds the protocol label, the application `context`, the
+/// group and its generator, the number of bases and each base in order, the
+/// statement `y` and the commitment `t`.
+pub fn challenge<G: Group, H: ChallengeHash>(
+    group: &G,
+    bases: &[G::Element],
+    y: &G::Element,
+    t: &G::Element,
+    context: &[u8],
+) -> G::Scalar {
+    let mut transcript = Transcript::<H>::new(PROTOCOL_LABEL);
+    transcript.append_message(b"context", context);
+    transcript.append_group(group);
+    transcript.append_message(b"n", &(bases.len() as u64).to_be_bytes());
+    for base in bases {
+        transcript.append_element(b"base", group, base);
+    }
+    transcript.append_element(b"y", group, y);
+    transcript.append_element(b"t", group, t);
+    transcript.challenge_scalar(b"c", group)
+}
+
+/// Returns `bases[0]^exponents[0] * ... * bases[n-1]^exponents[n-1]`.
+fn multi_exp<G: Group>(group: &G, bases: &[G::Element], exponents: &[G::Scalar]) -> G::Element {
+    bases
+        .iter()
+        .zip(exponents)
+        .fold(group.identity(), |acc, (base, k)| {
+            group.op(&acc, &group.exp(base, k))
+        })
+}
+
+/// Like [`multi_exp`], with secret exponents and [`Group::exp_secret`].
+fn multi_exp_secret<G: Group>(
+    group: &G,
+    bases: &[G::Element],
+    exponents: &[Secret<G::Scalar>],
+) -> G::Element {
+    bases
+        .iter()
+        .zip(exponents)
+        .fold(group.identity(), |acc, (base, k)| {
+            group.op(&acc, &group.exp_secret(base, k.expose_secret()))
+        })
+}
@@ -0,0 +1,480 @@
+//! Cramer-Damgård-Schoenmakers OR-proofs: knowledge of one of several
+//! discrete logarithms.
+//!
+//! The prover convinces the verifier that it knows `log_g(yi)` for at least
+//! one of the public keys `y1, ..., yn`, without revealing which. It runs one
+//! Schnorr instance per key and lets the verifier's challenge `c` fix only
+//! the sum of the branch challenges:
+//!
+//! 1. For every branch `j` it does not know, the prover picks a challenge
+//!    `cj` and fills the branch with the [simulator](crate::simulator). For
+//!    the known branch `i` it commits with [`Prover::commit`]. It sends all
+//!    the commitments `t1, ..., tn`.
+//! 2. The verifier answers with a challenge `c`.
+//! 3. The prover sets `ci = c - sum of the other cj`, answers it in the
+//!    known branch, and sends every `cj` followed by every `sj`. The verifier
+//!    checks that the `cj` sum to `c` and that `g^sj = tj * yj^cj` for every
+//!    branch.
+//!
+//! Simulated and real branches are identically distributed, so the
+//! transcript does not reveal which branch was real.
+//!
+//! [`OrProver`] and [`OrVerifier`] implement the [Sigma traits](crate::sigma),
+//! so an OR-statement can be one of the statements of an
+//! [`AndProver`](crate::AndProver).
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::thread_rng;
+use sha2::Sha256;
+
+use crate::encoding::{decode_proof, encode_proof, ScalarCount};
+use crate::error::{DecodeError, ProverError, VerifyError};
+use crate::group::Group;
+use crate::hash::{ChallengeHash, HashAlgorithm};
+use crate::params::PublicParams;
+use crate::schnorr::{Prover, Verifier};
+use crate::sigma::{
+    self, check_scalars, check_statement_length, ProverCommitted, SigmaNonces, SigmaProver,
+    SigmaVerifier, VerifierCommitted,
+};
+use crate::simulator::simulate;
+use crate::transcript::Transcript;
+
+/// Label that separates OR-proof transcripts from those of other protocols.
+const PROTOCOL_LABEL: &[u8] = b"zkp-or";
+
+/// A prover who knows the discrete logarithm of one of several public keys.
+pub struct OrProver<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    keys: Vec<G::Element>,
+    index: usize,
+    prover: Prover<G, H>,
+}
+
+/// A verifier of proofs of knowledge of one of several discrete logarithms.
+pub struct OrVerifier<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    verifier: Verifier<G, H>,
+    keys: Vec<G::Element>,
+}
+
+/// A non-interactive OR-proof in compact form.
+///
+/// Only the branch challenges `cj` and responses `sj` are sent; the verifier
+/// recomputes the commitments as `tj = g^sj * yj^-cj` and checks that hashing
+/// them gives the sum of the `cj`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrProof<G: Group> {
+    /// The branch challenges cj, one per public key.
+    pub c: Vec<G::Scalar>,
+    /// The branch responses sj, one per public key.
+    pub s: Vec<G::Scalar>,
+    /// The hash function the challenge was derived with.
+    pub hash: HashAlgorithm,
+}
+
+impl<G: Group> OrProof<G> {
+    /// Serializes the proof as the hash identifier byte followed by the
+    /// encodings of every `cj` and then every `sj`.
+    pub fn to_bytes(&self, group: &G) -> Vec<u8> {
+        encode_proof(group, self.hash, self.c.iter().chain(&self.s))
+    }
+
+    /// Parses a proof produced by [`OrProof::to_bytes`].
+    pub fn from_bytes(group: &G, bytes: &[u8]) -> Result<Self, DecodeError> {
+        // At least one branch, and the same number of cj and sj
+        let (hash, mut c) = decode_proof(group, bytes, ScalarCount::MultipleOf(2))?;
+        let s = c.split_off(c.len() / 2);
+        Ok(OrProof { c, s, hash })
+    }
+}
+
+impl<G: Group> OrProver<G> {
+    /// Creates a prover that knows the secret of `keys[index]`. Challenges
+    /// are derived with SHA-256.
+    ///
+    /// Fails if `index` is out of range, if the secret is zero, if the group
+    /// cannot exponentiate by it in constant time, or if `g^secret` is not
+    /// `keys[index]`.
+    pub fn new(
+        group: G,
+        keys: Vec<G::Element>,
+        index: usize,
+        secret: G::Scalar,
+    ) -> Result<Self, ProverError> {
+        Self::with_hash(group, keys, index, secret)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> OrProver<G, H> {
+    /// Like [`OrProver::new`], but derives challenges with `H`.
+    pub fn with_hash(
+        group: G,
+        keys: Vec<G::Element>,
+        index: usize,
+        secret: G::Scalar,
+    ) -> Result<Self, ProverError> {
+        if index >= keys.len() {
+            return Err(ProverError::IndexOutOfRange {
+                index,
+                len: keys.len(),
+            });
+        }
+        let prover = Prover::with_hash(group, secret)?;
+        if prover.public_key() != &keys[index] {
+            return Err(ProverError::WrongSecret);
+        }
+        Ok(OrProver {
+            keys,
+            index,
+            prover,
+        })
+    }
+
+    /// Returns the public keys `y1, ..., yn`.
+    pub fn public_keys(&self) -> &[G::Element] {
+        &self.keys
+    }
+
+    /// Step 1: simulates every branch but the known one and commits to the
+    /// known one.
+    pub fn commit(&self) -> ProverCommitted<'_, G> {
+        let group = self.prover.group();
+        let mut rng = thread_rng();
+        let simulated = self
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(j, y)| {
+                (j != self.index).then(|| simulate(group, y, &group.random_scalar(&mut rng)))
+            })
+            .collect::<Vec<_>>();
+        let committed = self.prover.commit();
+        let commitments = simulated
+            .iter()
+            .map(|branch| match branch {
+                Some((t, _, _)) => t.clone(),
+                None => committed.commitments()[0].clone(),
+            })
+            .collect();
+        let nonces = OrNonces {
+            group,
+            committed,
+            simulated,
+        };
+        ProverCommitted::new(commitments, Box::new(nonces))
+    }
+
+    /// Produces a non-interactive proof of knowledge of one of the secrets,
+    /// bound to the application `context`.
+    pub fn prove(&self, context: &[u8]) -> OrProof<G> {
+        let committed = self.commit();
+        let c = challenge::<G, H>(
+            self.prover.group(),
+            &self.keys,
+            committed.commitments(),
+            context,
+        );
+        let mut c = committed
+            .receive_challenge(c)
+            .respond()
+            .responses()
+            .to_vec();
+        let s = c.split_off(self.keys.len());
+        OrProof {
+            c,
+            s,
+            hash: H::ALGORITHM,
+        }
+    }
+}
+
+/// A simulated branch `(tj, cj, sj)`, or `None` for the known branch.
+type Branch<G> = Option<(
+    <G as Group>::Element,
+    <G as Group>::Scalar,
+    <G as Group>::Scalar,
+)>;
+
+/// The simulated branches and the session of the known branch.
+struct OrNonces<'a, G: Group> {
+    group: &'a G,
+    committed: ProverCommitted<'a, G>,
+    simulated: Vec<Branch<G>>,
+}
+
+impl<G: Group> SigmaNonces<G> for OrNonces<'_, G> {
+    /// Splits the challenge `c` into the branch challenges and answers the
+    /// known branch, returning every `cj` followed by every `sj`.
+    fn respond(self: Box<Self>, c: &G::Scalar) -> Vec<G::Scalar> {
+        let group = self.group;
+        // ci = c - sum of the simulated cj
+        let c_i = self
+            .simulated
+            .iter()
+            .flatten()
+            .fold(c.clone(), |acc, (_, c_j, _)| {
+                group.scalar_add(&acc, &group.scalar_neg(c_j))
+            });
+        let responded = self.committed.receive_challenge(c_i).respond();
+
+        let (mut c, s): (Vec<_>, Vec<_>) = self
+            .simulated
+            .into_iter()
+            .map(|branch| match branch {
+                Some((_, c_j, s_j)) => (c_j, s_j),
+                None => (
+                    responded.challenge().clone(),
+                    responded.responses()[0].clone(),
+                ),
+            })
+            .unzip();
+        c.extend(s);
+        c
+    }
+}
+
+impl<G: Group> OrVerifier<G> {
+    /// Creates a verifier for the public keys `keys` that derives challenges
+    /// with SHA-256.
+    pub fn new(group: G, keys: Vec<G::Element>) -> Self {
+        Self::with_hash(group, keys)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> OrVerifier<G, H> {
+    /// Creates a verifier for the public keys `keys` that derives challenges
+    /// with `H`.
+    pub fn with_hash(group: G, keys: Vec<G::Element>) -> Self {
+        OrVerifier {
+            verifier: Verifier::with_hash(group),
+            keys,
+        }
+    }
+
+    /// Checks that every public key is a group element.
+    fn check_keys(&self) -> Result<(), VerifyError> {
+        for y in &self.keys {
+            self.verifier
+                .group()
+                .check_element(y)
+                .map_err(VerifyError::InvalidPublicKey)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the branch challenges `cj` sum to `c`.
+    fn check_sum(&self, c: &G::Scalar, branch_challenges: &[G::Scalar]) -> Result<(), VerifyError> {
+        // c is not necessarily reduced, so compare modulo q
+        let group = self.verifier.group();
+        if group.scalar_to_biguint(&sum(group, branch_challenges)) != group.scalar_to_biguint(c) {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+
+    /// Starts an interactive session by receiving the commitments
+    /// `t1, ..., tn`.
+    ///
+    /// Fails early if any of the elements is not a group element.
+    pub fn receive_commitment(
+        &self,
+        commitments: &[G::Element],
+    ) -> Result<VerifierCommitted<'_, G>, VerifyError> {
+        VerifierCommitted::new(self, Vec::new(), commitments.to_vec())
+    }
+
+    /// Checks the transcript `(t, c, (cj), (sj))` with [`sigma::verify`]:
+    /// that the branch challenges `cj` sum to `c` and that every branch is an
+    /// accepting Schnorr transcript.
+    pub fn verify(
+        &self,
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        branch_challenges: &[G::Scalar],
+        responses: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        self.check_responses(branch_challenges, responses)?;
+        let s = [branch_challenges, responses].concat();
+        sigma::verify(self, &[], commitments, c, &s)
+    }
+
+    /// Checks that there is one branch challenge and one response per key.
+    fn check_responses(
+        &self,
+        branch_challenges: &[G::Scalar],
+        responses: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        for found in [branch_challenges.len(), responses.len()] {
+            if found != self.keys.len() {
+                return Err(VerifyError::ResponseCount {
+                    expected: self.keys.len(),
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a non-interactive proof of knowledge of one of the secrets,
+    /// made for the application `context`.
+    pub fn verify_proof(&self, proof: &OrProof<G>, context: &[u8]) -> Result<(), VerifyError> {
+        if proof.hash != H::ALGORITHM {
+            return Err(VerifyError::HashMismatch {
+                expected: H::ALGORITHM,
+                found: proof.hash,
+            });
+        }
+        self.check_keys()?;
+        self.check_responses(&proof.c, &proof.s)?;
+
+        let group = self.verifier.group();
+        check_scalars(group, proof.c.iter().chain(&proof.s))?;
+        let commitments = self.recompute(&proof.c, &proof.s);
+        if sum(group, &proof.c) != challenge::<G, H>(group, &self.keys, &commitments, context) {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+
+    /// Recomputes the commitments `tj = g^sj * yj^-cj`.
+    fn recompute(
+        &self,
+        branch_challenges: &[G::Scalar],
+        responses: &[G::Scalar],
+    ) -> Vec<G::Element> {
+        let group = self.verifier.group();
+        self.keys
+            .iter()
+            .zip(branch_challenges)
+            .zip(responses)
+            .map(|((y, c_j), s_j)| {
+                let g_s = group.exp(&group.generator(), s_j);
+                let y_c = group.exp(y, c_j);
+                group.op(&g_s, &group.invert(&y_c))
+            })
+            .collect()
+    }
+}
+
+impl<G: Group, H: ChallengeHash> SigmaProver<G> for OrProver<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        self.prover.group()
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        self.keys.clone()
+    }
+
+    fn public_values(&self) -> Vec<G::Element> {
+        Vec::new()
+    }
+
+    fn commit(&self) -> ProverCommitted<'_, G> {
+        OrProver::commit(self)
+    }
+}
+
+/// The verifier holds the keys, so they enter the challenge as parameters
+/// and the statement is empty. There is one commitment `tj` per key, and the
+/// responses are every `cj` followed by every `sj`.
+impl<G: Group, H: ChallengeHash> SigmaVerifier<G> for OrVerifier<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        self.verifier.group()
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        self.keys.clone()
+    }
+
+    fn commitment_count(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn response_count(&self) -> usize {
+        2 * self.keys.len()
+    }
+
+    fn check_statement(&self, public: &[G::Element]) -> Result<(), VerifyError> {
+        check_statement_length(public, 0)?;
+        self.check_keys()
+    }
+
+    fn check(
+        &self,
+        _public: &[G::Element],
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        let (branch_challenges, responses) = s.split_at(self.keys.len());
+        self.check_sum(c, branch_challenges)?;
+        for (((y, t), c_j), s_j) in self
+            .keys
+            .iter()
+            .zip(commitments)
+            .zip(branch_challenges)
+            .zip(responses)
+        {
+            let (y, t, s_j) = (
+                std::slice::from_ref(y),
+                std::slice::from_ref(t),
+                std::slice::from_ref(s_j),
+            );
+            self.verifier.check(y, t, c_j, s_j)?;
+        }
+        Ok(())
+    }
+
+    fn recompute_commitments(
+        &self,
+        _public: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<Vec<G::Element>, VerifyError> {
+        let (branch_challenges, responses) = s.split_at(self.keys.len());
+        self.check_sum(c, branch_challenges)?;
+        Ok(self.recompute(branch_challenges, responses))
+    }
+}
+
+/// Derives the Fiat-Shamir challenge for a proof of knowledge of one of the
+/// discrete logarithms of `keys`, with commitments `commitments`.
+///
+/// The transcript binds the protocol label, the application `context`, the
+/// group and its generator, the number of keys, every key and every
+/// commitment, in order.
+pub fn challenge<G: Group, H: ChallengeHash>(
+    group: &G,
+    keys: &[G::Element],
+    commitments: &[G::Element],
+    context: &[u8],
+) -> G::Scalar {
+    let mut transcript = Transcript::<H>::new(PROTOCOL_LABEL);
+    transcript.append_message(b"context", context);
+    transcript.append_group(group);
+    transcript.append_message(b"n", &(keys.len() as u64).to_be_bytes());
+    for y in keys {
+        transcript.append_element(b"y", group, y);
+    }
+    for t in commitments {
+        transcript.append_element(b"t", group, t);
+    }
+    transcript.challenge_scalar(b"c", group)
+}
+
+/// Returns the sum of `scalars` modulo q.
+fn sum<G: Group>(group: &G, scalars: &[G::Scalar]) -> G::Scalar {
+    scalars
+        .iter()
+        .fold(group.scalar_from_biguint(&BigUint::zero()), |acc, k| {
+            group.scalar_add(&acc, k)
+        })
+}
@@ -0,0 +1,189 @@
+//! Public parameters shared by the prover and the verifier.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{thread_rng, Rng};
+
+use crate::error::ParamsError;
+use crate::fips186::Provenance;
+use crate::primes::{is_probable_prime, random_odd, random_prime};
+
+/// Miller-Rabin rounds used when generating p and q.
+///
+/// FIPS 186-4 Table C.1 asks for at most 64 rounds for any (L, N) pair.
+pub(crate) const GENERATION_ROUNDS: usize = 64;
+
+/// Miller-Rabin rounds used by [`PublicParams::validate`].
+///
+/// A composite passes with probability at most 4^-64 = 2^-128.
+pub const DEFAULT_VALIDATION_ROUNDS: usize = 64;
+
+/// Represents the public parameters for the Schnorr protocol
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublicParams {
+    /// A prime number
+    pub p: BigUint,
+    /// A prime factor of p-1
+    pub q: BigUint,
+    /// A generator of the subgroup of order q
+    pub g: BigUint,
+    /// How p, q and g were derived, for parameters generated per FIPS 186-4
+    pub provenance: Option<Provenance>,
+}
+
+impl PublicParams {
+    /// Returns the toy group p = 23, q = 11, g = 4.
+    ///
+    /// These numbers are small enough to follow by hand and offer no security.
+    pub fn new() -> Self {
+        // Using small primes for demonstration
+        // q = 11 (a prime number)
+        // p = 2q + 1 = 23 (also prime)
+        let q = BigUint::from(11u32);
+        let p = BigUint::from(23u32);
+
+        // g = 4 is a generator of the subgroup of order 11 in Z_23
+        let g = BigUint::from(4u32);
+
+        PublicParams {
+            p,
+            q,
+            g,
+            provenance: None,
+        }
+    }
+
+    /// Checks that the parameters describe a prime-order subgroup of Z_p*.
+    ///
+    /// Parameters received from a peer should be validated before use, since
+    /// a malicious choice can make the protocol leak or accept anything.
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use zkp::{ParamsError, PublicParams};
+    ///
+    /// let mut params = PublicParams::new();
+    /// assert_eq!(params.validate(), Ok(()));
+    ///
+    /// params.g = BigUint::from(5u32); // 5 generates all of Z_23*, not the order-11 subgroup
+    /// assert_eq!(params.validate(), Err(ParamsError::GeneratorWrongOrder));
+    /// ```
+    pub fn validate(&self) -> Result<(), ParamsError> {
+        self.validate_with_rounds(DEFAULT_VALIDATION_ROUNDS)
+    }
+
+    /// Like [`PublicParams::validate`], but runs `rounds` Miller-Rabin rounds per
+    /// primality test, accepting a composite with probability at most 4^-rounds.
+    pub fn validate_with_rounds(&self, rounds: usize) -> Result<(), ParamsError> {
+        let mut rng = thread_rng();
+        let one = BigUint::one();
+
+        if self.g <= one || self.g >= self.p {
+            return Err(ParamsError::GeneratorOutOfRange);
+        }
+        if self.q.is_zero() || !((&self.p - 1u32) % &self.q).is_zero() {
+            return Err(ParamsError::OrderDoesNotDivide);
+        }
+        if !is_probable_prime(&self.q, rounds, &mut rng) {
+            return Err(ParamsError::OrderNotPrime);
+        }
+        if !is_probable_prime(&self.p, rounds, &mut rng) {
+            return Err(ParamsError::ModulusNotPrime);
+        }
+        if !self.g.modpow(&self.q, &self.p).is_one() {
+            return Err(ParamsError::GeneratorWrongOrder);
+        }
+        Ok(())
+    }
+
+    /// Generates fresh parameters with a `p_bits`-bit prime p and a `q_bits`-bit
+    /// prime q dividing p-1.
+    ///
+    /// When `p_bits == q_bits + 1` this produces a safe prime p = 2q + 1.
+    /// Otherwise it produces DSA-style parameters p = kq + 1, e.g. the
+    /// (L, N) pairs (2048, 256) or (3072, 256) from FIPS 186-4.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q_bits < 2` or `q_bits >= p_bits`.
+    pub fn generate<R: Rng + ?Sized>(p_bits: u64, q_bits: u64, rng: &mut R) -> Self {
+        assert!(q_bits >= 2, "q must have at least 2 bits");
+        assert!(q_bits < p_bits, "q must be shorter than p");
+
+        let (p, q) = if p_bits == q_bits + 1 {
+            Self::generate_safe_prime(q_bits, rng)
+        } else {
+            Self::generate_dsa_primes(p_bits, q_bits, rng)
+        };
+        let g = Self::find_generator(&p, &q, rng);
+
+        PublicParams {
+            p,
+            q,
+            g,
+            provenance: None,
+        }
+    }
+
+    /// Searches for a prime q of `q_bits` bits such that p = 2q + 1 is also prime.
+    fn generate_safe_prime<R: Rng + ?Sized>(q_bits: u64, rng: &mut R) -> (BigUint, BigUint) {
+        loop {
+            let q = if q_bits == 2 {
+                BigUint::from(3u32)
+            } else {
+                random_odd(q_bits, rng)
+            };
+            let p: BigUint = (&q << 1) + 1u32;
+            // Test p cheaply first: a single round rejects almost every candidate
+            if is_probable_prime(&p, 1, rng)
+                && is_probable_prime(&q, GENERATION_ROUNDS, rng)
+                && is_probable_prime(&p, GENERATION_ROUNDS, rng)
+            {
+                return (p, q);
+            }
+        }
+    }
+
+    /// Picks a prime q and then searches for a `p_bits`-bit prime p = kq + 1,
+    /// following the structure of FIPS 186-4 A.1.1.2.
+    fn generate_dsa_primes<R: Rng + ?Sized>(
+        p_bits: u64,
+        q_bits: u64,
+        rng: &mut R,
+    ) -> (BigUint, BigUint) {
+        loop {
+            let q = random_prime(q_bits, GENERATION_ROUNDS, rng);
+            let two_q: BigUint = &q << 1;
+
+            // After 4L unsuccessful candidates, start over with a new q
+            for _ in 0..4 * p_bits {
+                let x = random_odd(p_bits, rng);
+                // Round x down to the nearest p ≡ 1 (mod 2q)
+                let p = &x - (&x % &two_q) + 1u32;
+                if p.bits() == p_bits && is_probable_prime(&p, GENERATION_ROUNDS, rng) {
+                    return (p, q);
+                }
+            }
+        }
+    }
+
+    /// Returns an element of order q in Z_p*, computed as h^((p-1)/q) for a random h.
+    fn find_generator<R: Rng + ?Sized>(p: &BigUint, q: &BigUint, rng: &mut R) -> BigUint {
+        let two = BigUint::from(2u32);
+        let p_minus_one = p - 1u32;
+        let cofactor = &p_minus_one / q;
+        loop {
+            let h = rng.gen_biguint_range(&two, &p_minus_one);
+            let g = h.modpow(&cofactor, p);
+            if !g.is_one() {
+                return g;
+            }
+        }
+    }
+}
+
+impl Default for PublicParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,86 @@
+//! Probabilistic primality testing and prime generation.
+//!
+//! `num-bigint` has no primality test of its own, so this module provides a
+//! Miller-Rabin test that works directly on [`BigUint`].
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::Rng;
+
+/// Odd primes below 256, used to discard most composites before running
+/// the (much more expensive) Miller-Rabin rounds.
+const SMALL_PRIMES: [u32; 53] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
+];
+
+/// Returns `true` if `n` is probably prime.
+///
+/// Runs `rounds` Miller-Rabin rounds with random bases drawn from `rng`. A
+/// composite passes each round with probability at most 1/4, so the chance of
+/// accepting a composite is at most `4^-rounds`.
+pub fn is_probable_prime<R: Rng + ?Sized>(n: &BigUint, rounds: usize, rng: &mut R) -> bool {
+    let two = BigUint::from(2u32);
+    if n < &two {
+        return false;
+    }
+    if n == &two {
+        return true;
+    }
+    if !n.bit(0) {
+        return false;
+    }
+    for &small in SMALL_PRIMES.iter() {
+        if n == &BigUint::from(small) {
+            return true;
+        }
+        if (n % small).is_zero() {
+            return false;
+        }
+    }
+
+    // Write n - 1 = 2^s * d with d odd
+    let n_minus_one = n - 1u32;
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+
+    'witness: for _ in 0..rounds {
+        // Pick a random base a in [2, n-2]
+        let a = rng.gen_biguint_range(&two, &n_minus_one);
+        let mut x = a.modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Returns a random odd integer with exactly `bits` bits.
+pub(crate) fn random_odd<R: Rng + ?Sized>(bits: u64, rng: &mut R) -> BigUint {
+    let mut n = rng.gen_biguint(bits);
+    n.set_bit(bits - 1, true);
+    n.set_bit(0, true);
+    n
+}
+
+/// Returns a random probable prime with exactly `bits` bits.
+pub fn random_prime<R: Rng + ?Sized>(bits: u64, rounds: usize, rng: &mut R) -> BigUint {
+    assert!(bits >= 2, "a prime needs at least 2 bits");
+    if bits == 2 {
+        return BigUint::from(if rng.gen::<bool>() { 2u32 } else { 3u32 });
+    }
+    loop {
+        let candidate = random_odd(bits, rng);
+        if is_probable_prime(&candidate, rounds, rng) {
+            return candidate;
+        }
+    }
+}
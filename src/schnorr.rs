@@ -0,0 +1,440 @@
+//! The Schnorr protocol for proving knowledge of a discrete logarithm.
+//!
+//! The protocol runs in one of two clearly separate modes:
+//!
+//! - **Interactive**: the prover and verifier each move through the typestate
+//!   [sessions](crate::sigma) that every Sigma protocol shares. The prover
+//!   commits with [`Prover::commit`], the verifier receives the commitment
+//!   with [`Verifier::receive_commitment`] and answers with a uniformly random
+//!   challenge, and the prover's response is checked against that challenge.
+//!   Nobody but this verifier is convinced, since the transcript could have
+//!   been simulated.
+//! - **Non-interactive**: [`Prover::prove`] derives the challenge from a
+//!   Fiat-Shamir transcript with [`challenge`] and produces a
+//!   [`SchnorrProof`], which anyone can check with [`Verifier::verify_proof`].
+//!   [`Prover::prove_deterministic`] and [`Prover::prove_hedged`] derive the
+//!   nonce as in RFC 6979 instead of relying on the random number generator
+//!   alone.
+
+use std::marker::PhantomData;
+
+use rand::{thread_rng, RngCore};
+use sha2::Sha256;
+
+use crate::encoding::{decode_proof, encode_proof, ScalarCount};
+use crate::error::{DecodeError, ParamsError, ProverError, VerifyError};
+use crate::group::Group;
+use crate::hash::{ChallengeHash, HashAlgorithm};
+use crate::nonce::rfc6979_nonce;
+use crate::params::PublicParams;
+use crate::secret::Secret;
+use crate::sigma::{
+    self, check_statement_length, LinearNonces, ProverCommitted, SigmaProver, SigmaVerifier,
+    VerifierCommitted,
+};
+use crate::transcript::Transcript;
+
+/// Label that separates Schnorr transcripts from those of other protocols.
+const PROTOCOL_LABEL: &[u8] = b"zkp-schnorr";
+
+/// Represents a prover who knows the secret
+pub struct Prover<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    x: Secret<G::Scalar>, // The secret (private key)
+    y: G::Element,        // The public commitment (public key)
+    hash: PhantomData<H>,
+}
+
+/// Represents a verifier who wants to be convinced
+pub struct Verifier<G: Group = PublicParams, H: ChallengeHash = Sha256> {
+    group: G,
+    hash: PhantomData<H>,
+}
+
+/// A non-interactive Schnorr proof in compact form.
+///
+/// Only the challenge `c` and the response `s` are sent; the verifier
+/// recomputes the commitment as `t = g^s * y^-c` and checks that hashing it
+/// gives back `c`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchnorrProof<G: Group> {
+    /// The challenge c.
+    pub c: G::Scalar,
+    /// The response s = r + c*x mod q.
+    pub s: G::Scalar,
+    /// The hash function c was derived with.
+    pub hash: HashAlgorithm,
+}
+
+impl<G: Group> SchnorrProof<G> {
+    /// Recomputes the commitment `t = g^s * y^-c` the proof was made with,
+    /// for the public key `y`.
+    pub fn commitment(&self, group: &G, y: &G::Element) -> G::Element {
+        let g_s = group.exp(&group.generator(), &self.s);
+        let y_c = group.exp(y, &self.c);
+        group.op(&g_s, &group.invert(&y_c))
+    }
+
+    /// Serializes the proof as the hash identifier byte followed by the
+    /// encodings of `c` and `s`.
+    pub fn to_bytes(&self, group: &G) -> Vec<u8> {
+        encode_proof(group, self.hash, [&self.c, &self.s])
+    }
+
+    /// Parses a proof produced by [`SchnorrProof::to_bytes`].
+    pub fn from_bytes(group: &G, bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (hash, mut scalars) = decode_proof(group, bytes, ScalarCount::Exactly(2))?;
+        let s = scalars.pop().expect("two scalars were decoded");
+        let c = scalars.pop().expect("two scalars were decoded");
+        Ok(SchnorrProof { c, s, hash })
+    }
+}
+
+impl<G: Group> Prover<G> {
+    /// Creates a prover for the secret `x`, deriving the public key `y = g^x`.
+    /// Challenges are derived with SHA-256.
+    ///
+    /// Fails if `x ≡ 0 (mod q)`: the public key would then be the identity,
+    /// which reveals the secret to everyone. Also fails if the group cannot
+    /// exponentiate by `x` in constant time (see [`Group::supports_exp_secret`]).
+    pub fn new(group: G, secret: G::Scalar) -> Result<Self, ProverError> {
+        Self::with_hash(group, secret)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> Prover<G, H> {
+    /// Like [`Prover::new`], but derives challenges with `H`.
+    pub fn with_hash(group: G, secret: G::Scalar) -> Result<Self, ProverError> {
+        if !group.supports_exp_secret() {
+            return Err(ProverError::UnsupportedGroup);
+        }
+        if group.scalar_is_zero(&secret) {
+            return Err(ProverError::ZeroSecret);
+        }
+        let y = group.exp_secret(&group.generator(), &secret);
+        Ok(Prover {
+            group,
+            x: Secret::new(secret),
+            y,
+            hash: PhantomData,
+        })
+    }
+
+    /// Returns the public key `y = g^x`.
+    pub fn public_key(&self) -> &G::Element {
+        &self.y
+    }
+
+    /// Returns the group this prover works in.
+    pub fn group(&self) -> &G {
+        &self.group
+    }
+
+    /// Step 1: picks a random nonce `r` in [1, q-1] and commits to it with
+    /// `t = g^r`.
+    pub fn commit(&self) -> ProverCommitted<'_, G> {
+        self.commit_to(LinearNonces::random(
+            &self.group,
+            std::slice::from_ref(&self.x),
+        ))
+    }
+
+    /// Commits to the nonce `r` with `t = g^r`.
+    ///
+    /// Answering two challenges with the same nonce reveals the secret, which
+    /// is exactly what the knowledge extractor demonstrates; nothing else in
+    /// the crate may commit to a nonce twice.
+    pub(crate) fn commit_with(&self, r: Secret<G::Scalar>) -> ProverCommitted<'_, G> {
+        let x = std::slice::from_ref(&self.x);
+        self.commit_to(LinearNonces::new(&self.group, x, vec![r]))
+    }
+
+    /// Commits to `nonces` with `t = g^r`.
+    fn commit_to<'a>(&'a self, nonces: LinearNonces<'a, G>) -> ProverCommitted<'a, G> {
+        let r = nonces.nonces()[0].expose_secret();
+        let t = self.group.exp_secret(&self.group.generator(), r);
+        ProverCommitted::new(vec![t], Box::new(nonces))
+    }
+
+    /// Produces a non-interactive proof of knowledge of `x`, bound to the
+    /// application `context`.
+    ///
+    /// Runs all three moves internally, deriving the challenge with
+    /// [`challenge`], so the nonce never leaves the prover.
+    pub fn prove(&self, context: &[u8]) -> SchnorrProof<G> {
+        self.prove_committed(self.commit(), context)
+    }
+
+    /// Like [`Prover::prove`], but derives the nonce deterministically from
+    /// the secret and the statement with [`rfc6979_nonce`].
+    ///
+    /// The same prover and `context` always produce the same proof, so a
+    /// broken random number generator cannot leak the secret.
+    pub fn prove_deterministic(&self, context: &[u8]) -> SchnorrProof<G> {
+        let h1 = nonce_message::<G, H>(&self.group, &self.y, context);
+        let r = rfc6979_nonce::<G, H>(&self.group, self.x.expose_secret(), &h1, &[]);
+        self.prove_committed(self.commit_with(r), context)
+    }
+
+    /// Like [`Prover::prove_deterministic`], but also mixes 32 fresh random
+    /// bytes into the nonce derivation.
+    ///
+    /// The nonce is unpredictable as long as either the random number
+    /// generator or the secret is sound, which also frustrates fault attacks
+    /// that rely on repeating a deterministic computation.
+    pub fn prove_hedged(&self, context: &[u8]) -> SchnorrProof<G> {
+        let mut extra = [0u8; 32];
+        thread_rng().fill_bytes(&mut extra);
+        let h1 = nonce_message::<G, H>(&self.group, &self.y, context);
+        let r = rfc6979_nonce::<G, H>(&self.group, self.x.expose_secret(), &h1, &extra);
+        self.prove_committed(self.commit_with(r), context)
+    }
+
+    /// Answers the Fiat-Shamir challenge for `committed`.
+    fn prove_committed(
+        &self,
+        committed: ProverCommitted<'_, G>,
+        context: &[u8],
+    ) -> SchnorrProof<G> {
+        let c = challenge::<G, H>(&self.group, &self.y, &committed.commitments()[0], context);
+        let responded = committed.receive_challenge(c).respond();
+        SchnorrProof {
+            c: responded.challenge().clone(),
+            s: responded.responses()[0].clone(),
+            hash: H::ALGORITHM,
+        }
+    }
+}
+
+impl<G: Group> Verifier<G> {
+    /// Creates a verifier for the given group that derives challenges with SHA-256.
+    ///
+    /// The group is trusted as-is; use [`Verifier::new_checked`] for
+    /// parameters that come from an untrusted source.
+    pub fn new(group: G) -> Self {
+        Self::with_hash(group)
+    }
+}
+
+impl<G: Group, H: ChallengeHash> Verifier<G, H> {
+    /// Creates a verifier for the given group that derives challenges with `H`.
+    ///
+    /// ```
+    /// use sha3::Sha3_256;
+    /// use zkp::{PublicParams, Verifier};
+    ///
+    /// let verifier = Verifier::<_, Sha3_256>::with_hash(PublicParams::new());
+    /// ```
+    pub fn with_hash(group: G) -> Self {
+        Verifier {
+            group,
+            hash: PhantomData,
+        }
+    }
+
+    /// Returns the group this verifier works in.
+    pub fn group(&self) -> &G {
+        &self.group
+    }
+
+    /// Starts an interactive session by receiving the commitment `t` for the
+    /// public key `y`.
+    ///
+    /// Fails early if `y` or `t` is not a group element.
+    pub fn receive_commitment(
+        &self,
+        y: &G::Element,
+        t: &G::Element,
+    ) -> Result<VerifierCommitted<'_, G>, VerifyError> {
+        VerifierCommitted::new(self, vec![y.clone()], vec![t.clone()])
+    }
+
+    /// Checks the transcript `(t, c, s)` against the public key `y` with
+    /// [`sigma::verify`], i.e. that `g^s = t * y^c`.
+    ///
+    /// ```
+    /// use num_bigint::BigUint;
+    /// use zkp::{ElementError, PublicParams, Verifier, VerifyError};
+    ///
+    /// let verifier = Verifier::new(PublicParams::new());
+    /// let one = BigUint::from(1u32);
+    /// // 22 = -1 mod 23 has order 2, so it is not in the subgroup of order 11
+    /// let y = BigUint::from(22u32);
+    /// assert_eq!(
+    ///     verifier.verify(&one, &one, &one, &y),
+    ///     Err(VerifyError::InvalidPublicKey(ElementError::NotInSubgroup))
+    /// );
+    /// ```
+    pub fn verify(
+        &self,
+        t: &G::Element,
+        c: &G::Scalar,
+        s: &G::Scalar,
+        y: &G::Element,
+    ) -> Result<(), VerifyError> {
+        sigma::verify(
+            self,
+            std::slice::from_ref(y),
+            std::slice::from_ref(t),
+            c,
+            std::slice::from_ref(s),
+        )
+    }
+
+    /// Checks a non-interactive proof of knowledge of log_g(y) made for the
+    /// application `context`.
+    pub fn verify_proof(
+        &self,
+        y: &G::Element,
+        proof: &SchnorrProof<G>,
+        context: &[u8],
+    ) -> Result<(), VerifyError> {
+        if proof.hash != H::ALGORITHM {
+            return Err(VerifyError::HashMismatch {
+                expected: H::ALGORITHM,
+                found: proof.hash,
+            });
+        }
+        let t = sigma::recompute_commitments(
+            self,
+            std::slice::from_ref(y),
+            &proof.c,
+            std::slice::from_ref(&proof.s),
+        )?;
+        if challenge::<G, H>(&self.group, y, &t[0], context) != proof.c {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+}
+
+impl Verifier<PublicParams> {
+    /// Creates a verifier after checking the parameters with [`PublicParams::validate`].
+    pub fn new_checked(params: PublicParams) -> Result<Self, ParamsError> {
+        params.validate()?;
+        Ok(Verifier::new(params))
+    }
+}
+
+impl<G: Group, H: ChallengeHash> SigmaProver<G> for Prover<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        Vec::new()
+    }
+
+    fn public_values(&self) -> Vec<G::Element> {
+        vec![self.y.clone()]
+    }
+
+    fn commit(&self) -> ProverCommitted<'_, G> {
+        Prover::commit(self)
+    }
+}
+
+/// The statement is `[y]`, with one commitment `t` and one response `s`.
+impl<G: Group, H: ChallengeHash> SigmaVerifier<G> for Verifier<G, H> {
+    fn label(&self) -> &'static [u8] {
+        PROTOCOL_LABEL
+    }
+
+    fn group(&self) -> &G {
+        &self.group
+    }
+
+    fn parameters(&self) -> Vec<G::Element> {
+        Vec::new()
+    }
+
+    fn commitment_count(&self) -> usize {
+        1
+    }
+
+    fn response_count(&self) -> usize {
+        1
+    }
+
+    fn check_statement(&self, public: &[G::Element]) -> Result<(), VerifyError> {
+        check_statement_length(public, 1)?;
+        self.group
+            .check_element(&public[0])
+            .map_err(VerifyError::InvalidPublicKey)
+    }
+
+    fn check(
+        &self,
+        public: &[G::Element],
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError> {
+        // Verify that g^s = t * y^c
+        let left = self.group.exp(&self.group.generator(), &s[0]);
+        let right = self
+            .group
+            .op(&commitments[0], &self.group.exp(&public[0], c));
+        if left != right {
+            return Err(VerifyError::EquationFailed);
+        }
+        Ok(())
+    }
+
+    fn recompute_commitments(
+        &self,
+        public: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<Vec<G::Element>, VerifyError> {
+        let proof = SchnorrProof {
+            c: c.clone(),
+            s: s[0].clone(),
+            hash: H::ALGORITHM,
+        };
+        Ok(vec![proof.commitment(&self.group, &public[0])])
+    }
+}
+
+/// Derives the Fiat-Shamir challenge for a proof of knowledge of log_g(y)
+/// with commitment `t`.
+///
+/// The transcript binds the protocol label, the application `context`, the
+/// group and its generator, the statement `y` and the commitment `t`, so a
+/// challenge cannot be replayed for a different statement, group or
+/// application. Provers and verifiers must both derive challenges here, with
+/// the same hash function `H`.
+pub fn challenge<G: Group, H: ChallengeHash>(
+    group: &G,
+    y: &G::Element,
+    t: &G::Element,
+    context: &[u8],
+) -> G::Scalar {
+    let mut transcript = statement_transcript::<G, H>(group, y, context);
+    transcript.append_element(b"t", group, t);
+    transcript.challenge_scalar(b"c", group)
+}
+
+/// Digests the statement that deterministic nonces are derived from: the
+/// same transcript as [`challenge`], up to but excluding the commitment.
+fn nonce_message<G: Group, H: ChallengeHash>(group: &G, y: &G::Element, context: &[u8]) -> Vec<u8> {
+    statement_transcript::<G, H>(group, y, context).challenge_bytes(b"nonce")
+}
+
+/// Starts a transcript that binds the application `context`, the group and
+/// the statement `y`.
+fn statement_transcript<G: Group, H: ChallengeHash>(
+    group: &G,
+    y: &G::Element,
+    context: &[u8],
+) -> Transcript<H> {
+    let mut transcript = Transcript::<H>::new(PROTOCOL_LABEL);
+    transcript.append_message(b"context", context);
+    transcript.append_group(group);
+    transcript.append_element(b"y", group, y);
+    transcript
+}
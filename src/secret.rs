@@ -0,0 +1,76 @@
+//! Secret scalars that are cleared from memory when dropped.
+//!
+//! The prover's secret `x` and its nonces are held in a [`Secret`], which
+//! overwrites the value when it goes out of scope, never prints it and only
+//! hands it out through [`Secret::expose_secret`].
+//!
+//! The curve scalars are cleared with the `zeroize` crate. For the
+//! [`BigUint`] scalars of [`PublicParams`](crate::PublicParams), clearing is
+//! best-effort: `num-bigint` does not expose its limbs, so they cannot be
+//! overwritten with volatile writes, and copies made by earlier arithmetic or
+//! by reallocation are out of reach.
+
+use std::fmt;
+use std::hint::black_box;
+
+use num_bigint::BigUint;
+
+/// Values that can overwrite themselves with zeros.
+///
+/// [`Group::Scalar`](crate::Group::Scalar) requires this so that secret
+/// scalars of every backend can be wrapped in a [`Secret`].
+pub trait Zeroizable {
+    /// Overwrites the value in place.
+    fn zeroize(&mut self);
+}
+
+/// Best-effort: see the [module documentation](self).
+impl Zeroizable for BigUint {
+    fn zeroize(&mut self) {
+        // Clear from the lowest bit up, so every limb is overwritten in place
+        // before the top limb becomes zero and the limb vector is truncated.
+        // The writes are not volatile; `black_box` only discourages the
+        // compiler from dropping them as dead stores.
+        for bit in 0..self.bits() {
+            self.set_bit(bit, false);
+        }
+        black_box(&*self);
+    }
+}
+
+/// A secret value that is zeroized on drop.
+///
+/// Its `Debug` output is redacted and it implements neither `Display` nor
+/// `Clone`, so the value can only leave the wrapper through an explicit
+/// [`Secret::expose_secret`].
+pub struct Secret<S: Zeroizable>(S);
+
+impl<S: Zeroizable> Secret<S> {
+    /// Takes ownership of `value`.
+    pub fn new(value: S) -> Self {
+        Secret(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub fn expose_secret(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S: Zeroizable> From<S> for Secret<S> {
+    fn from(value: S) -> Self {
+        Secret::new(value)
+    }
+}
+
+impl<S: Zeroizable> Drop for Secret<S> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<S: Zeroizable> fmt::Debug for Secret<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
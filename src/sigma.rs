@@ -0,0 +1,406 @@
+//! The interface shared by the Sigma protocols in this crate, and the
+//! sessions that run them interactively.
+//!
+//! A Sigma protocol runs in three moves: the prover commits to fresh nonces,
+//! the verifier answers with a challenge `c`, and the prover responds. The
+//! Schnorr, DLEQ, Okamoto, OR and AND provers and verifiers implement
+//! [`SigmaProver`] and [`SigmaVerifier`], which lets
+//! [`AndProver`](crate::AndProver) and [`AndVerifier`](crate::AndVerifier)
+//! compose them.
+//!
+//! Each protocol only supplies how to commit, how to respond and how to
+//! check; the typestate sessions are the same for all of them. The prover
+//! moves through [`ProverCommitted`] and [`ProverChallenged`], the verifier
+//! through [`VerifierCommitted`] and [`VerifierChallenged`], and both end
+//! with the public transcript [`Responded`]. Every state consumes the
+//! previous one, so a nonce cannot answer two challenges and the moves
+//! cannot be reordered:
+//!
+//! ```compile_fail
+//! use num_bigint::BigUint;
+//! use zkp::{Prover, PublicParams};
+//!
+//! let prover = Prover::new(PublicParams::new(), BigUint::from(6u32)).unwrap();
+//! let committed = prover.commit();
+//! let first = committed.receive_challenge(BigUint::from(1u32)).respond();
+//! let second = committed.receive_challenge(BigUint::from(2u32)).respond();
+//! ```
+
+use rand::thread_rng;
+
+use crate::error::VerifyError;
+use crate::group::Group;
+use crate::secret::Secret;
+
+/// The prover's side of a Sigma protocol.
+pub trait SigmaProver<G: Group> {
+    /// Returns the label that identifies the protocol.
+    fn label(&self) -> &'static [u8];
+
+    /// Returns the group the protocol runs in.
+    fn group(&self) -> &G;
+
+    /// Returns the fixed parameters of the protocol beyond the group, such as
+    /// extra bases.
+    fn parameters(&self) -> Vec<G::Element>;
+
+    /// Returns the public values of the statement, such as the public key.
+    fn public_values(&self) -> Vec<G::Element>;
+
+    /// Step 1: commits to fresh nonces.
+    fn commit(&self) -> ProverCommitted<'_, G>;
+}
+
+/// The nonces behind a prover's commitments.
+pub trait SigmaNonces<G: Group> {
+    /// Step 3: answers the challenge `c`, consuming the nonces.
+    fn respond(self: Box<Self>, c: &G::Scalar) -> Vec<G::Scalar>;
+}
+
+/// The verifier's side of a Sigma protocol.
+///
+/// [`verify`] and [`recompute_commitments`] check the shape of their inputs
+/// before calling [`SigmaVerifier::check`] and
+/// [`SigmaVerifier::recompute_commitments`], which may rely on it.
+pub trait SigmaVerifier<G: Group> {
+    /// Returns the label that identifies the protocol.
+    fn label(&self) -> &'static [u8];
+
+    /// Returns the group the protocol runs in.
+    fn group(&self) -> &G;
+
+    /// Returns the fixed parameters of the protocol beyond the group, such as
+    /// extra bases.
+    fn parameters(&self) -> Vec<G::Element>;
+
+    /// Returns the number of commitments the protocol sends.
+    fn commitment_count(&self) -> usize;
+
+    /// Returns the number of responses the protocol sends.
+    fn response_count(&self) -> usize;
+
+    /// Checks that `public` has the right number of public values and that
+    /// each is a group element.
+    fn check_statement(&self, public: &[G::Element]) -> Result<(), VerifyError>;
+
+    /// Checks the verification equation for the transcript
+    /// `(commitments, c, s)` and the statement `public`.
+    fn check(
+        &self,
+        public: &[G::Element],
+        commitments: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<(), VerifyError>;
+
+    /// Recomputes the commitments that make `(c, s)` an accepting transcript
+    /// for the statement `public`.
+    fn recompute_commitments(
+        &self,
+        public: &[G::Element],
+        c: &G::Scalar,
+        s: &[G::Scalar],
+    ) -> Result<Vec<G::Element>, VerifyError>;
+}
+
+/// Checks the transcript `(commitments, c, s)` against the statement with
+/// public values `public`.
+///
+/// This only checks the transcript and says nothing about how `c` was
+/// chosen; an interactive verifier should use
+/// [`VerifierChallenged::receive_response`], which checks against its own
+/// challenge.
+///
+/// The statement and the commitments must be members of the prime-order
+/// group; otherwise a cheating prover could use elements of small order to
+/// pass with probability far above 1/q.
+pub fn verify<G: Group, V: SigmaVerifier<G> + ?Sized>(
+    verifier: &V,
+    public: &[G::Element],
+    commitments: &[G::Element],
+    c: &G::Scalar,
+    s: &[G::Scalar],
+) -> Result<(), VerifyError> {
+    verifier.check_statement(public)?;
+    check_commitments(verifier, commitments)?;
+    check_response_count(s, verifier.response_count())?;
+    check_scalars(verifier.group(), std::iter::once(c).chain(s))?;
+    verifier.check(public, commitments, c, s)
+}
+
+/// Recomputes the commitments that make `(c, s)` an accepting transcript
+/// for the statement with public values `public`, as a compact proof is
+/// checked.
+pub fn recompute_commitments<G: Group, V: SigmaVerifier<G> + ?Sized>(
+    verifier: &V,
+    public: &[G::Element],
+    c: &G::Scalar,
+    s: &[G::Scalar],
+) -> Result<Vec<G::Element>, VerifyError> {
+    verifier.check_statement(public)?;
+    check_response_count(s, verifier.response_count())?;
+    check_scalars(verifier.group(), std::iter::once(c).chain(s))?;
+    verifier.recompute_commitments(public, c, s)
+}
+
+/// Checks that there are as many commitments as the protocol sends and that
+/// each is a group element.
+fn check_commitments<G: Group, V: SigmaVerifier<G> + ?Sized>(
+    verifier: &V,
+    commitments: &[G::Element],
+) -> Result<(), VerifyError> {
+    check_commitment_count(commitments, verifier.commitment_count())?;
+    for t in commitments {
+        verifier
+            .group()
+            .check_element(t)
+            .map_err(VerifyError::InvalidCommitment)?;
+    }
+    Ok(())
+}
+
+/// Prover session after step 1, holding the commitments and the nonces
+/// behind them.
+pub struct ProverCommitted<'a, G: Group> {
+    commitments: Vec<G::Element>,
+    nonces: Box<dyn SigmaNonces<G> + 'a>,
+}
+
+impl<'a, G: Group> ProverCommitted<'a, G> {
+    /// Starts a session from `commitments` and the `nonces` they commit to.
+    pub fn new(commitments: Vec<G::Element>, nonces: Box<dyn SigmaNonces<G> + 'a>) -> Self {
+        ProverCommitted {
+            commitments,
+            nonces,
+        }
+    }
+
+    /// Returns the commitments to send to the verifier.
+    pub fn commitments(&self) -> &[G::Element] {
+        &self.commitments
+    }
+
+    /// Receives the verifier's challenge `c`.
+    pub fn receive_challenge(self, c: G::Scalar) -> ProverChallenged<'a, G> {
+        ProverChallenged {
+            commitments: self.commitments,
+            nonces: self.nonces,
+            c,
+        }
+    }
+}
+
+/// Prover session after receiving the challenge.
+pub struct ProverChallenged<'a, G: Group> {
+    commitments: Vec<G::Element>,
+    nonces: Box<dyn SigmaNonces<G> + 'a>,
+    c: G::Scalar,
+}
+
+impl<G: Group> ProverChallenged<'_, G> {
+    /// Returns the challenge `c` this session will answer.
+    pub fn challenge(&self) -> &G::Scalar {
+        &self.c
+    }
+
+    /// Step 3: answers the challenge.
+    ///
+    /// The nonces are zeroized here; only the public transcript is kept.
+    pub fn respond(self) -> Responded<G> {
+        let s = self.nonces.respond(&self.c);
+        Responded {
+            commitments: self.commitments,
+            c: self.c,
+            s,
+        }
+    }
+}
+
+/// Verifier session after receiving the commitments.
+pub struct VerifierCommitted<'a, G: Group> {
+    verifier: &'a dyn SigmaVerifier<G>,
+    public: Vec<G::Element>,
+    commitments: Vec<G::Element>,
+}
+
+impl<'a, G: Group> VerifierCommitted<'a, G> {
+    /// Starts a session by receiving `commitments` for the statement with
+    /// public values `public`.
+    ///
+    /// Fails early if the statement or the commitments are malformed or not
+    /// group elements.
+    pub fn new(
+        verifier: &'a dyn SigmaVerifier<G>,
+        public: Vec<G::Element>,
+        commitments: Vec<G::Element>,
+    ) -> Result<Self, VerifyError> {
+        verifier.check_statement(&public)?;
+        check_commitments(verifier, &commitments)?;
+        Ok(VerifierCommitted {
+            verifier,
+            public,
+            commitments,
+        })
+    }
+
+    /// Step 2: samples a uniformly random challenge `c`.
+    pub fn challenge(self) -> VerifierChallenged<'a, G> {
+        let c = self.verifier.group().random_scalar(&mut thread_rng());
+        VerifierChallenged {
+            verifier: self.verifier,
+            public: self.public,
+            commitments: self.commitments,
+            c,
+        }
+    }
+}
+
+/// Verifier session after sampling the challenge.
+pub struct VerifierChallenged<'a, G: Group> {
+    verifier: &'a dyn SigmaVerifier<G>,
+    public: Vec<G::Element>,
+    commitments: Vec<G::Element>,
+    c: G::Scalar,
+}
+
+impl<G: Group> VerifierChallenged<'_, G> {
+    /// Returns the random challenge `c` to send to the prover.
+    pub fn challenge(&self) -> &G::Scalar {
+        &self.c
+    }
+
+    /// Step 4: receives the prover's responses `s` and checks them against
+    /// this session's commitments and challenge.
+    ///
+    /// Consumes the session, so each challenge is answered at most once.
+    pub fn receive_response(self, s: Vec<G::Scalar>) -> Result<Responded<G>, VerifyError> {
+        check_response_count(&s, self.verifier.response_count())?;
+        check_scalars(self.verifier.group(), &s)?;
+        self.verifier
+            .check(&self.public, &self.commitments, &self.c, &s)?;
+        Ok(Responded {
+            commitments: self.commitments,
+            c: self.c,
+            s,
+        })
+    }
+}
+
+/// A session after step 3 or 4: the public transcript `(t, c, s)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Responded<G: Group> {
+    commitments: Vec<G::Element>,
+    c: G::Scalar,
+    s: Vec<G::Scalar>,
+}
+
+impl<G: Group> Responded<G> {
+    /// Returns the commitments `t`.
+    pub fn commitments(&self) -> &[G::Element] {
+        &self.commitments
+    }
+
+    /// Returns the challenge `c`.
+    pub fn challenge(&self) -> &G::Scalar {
+        &self.c
+    }
+
+    /// Returns the responses `s`.
+    pub fn responses(&self) -> &[G::Scalar] {
+        &self.s
+    }
+}
+
+/// Nonces `ri` for the secrets `xi` of a linear relation, such as a discrete
+/// logarithm or a representation, answered with `si = ri + c*xi mod q`.
+pub(crate) struct LinearNonces<'a, G: Group> {
+    group: &'a G,
+    x: &'a [Secret<G::Scalar>],
+    r: Vec<Secret<G::Scalar>>,
+}
+
+impl<'a, G: Group> LinearNonces<'a, G> {
+    /// Draws one nonce in [1, q-1] per secret.
+    pub(crate) fn random(group: &'a G, x: &'a [Secret<G::Scalar>]) -> Self {
+        let mut rng = thread_rng();
+        let r = x
+            .iter()
+            .map(|_| Secret::new(group.random_nonzero_scalar(&mut rng)))
+            .collect();
+        LinearNonces { group, x, r }
+    }
+
+    /// Uses the nonces `r`, one per secret.
+    pub(crate) fn new(group: &'a G, x: &'a [Secret<G::Scalar>], r: Vec<Secret<G::Scalar>>) -> Self {
+        debug_assert_eq!(x.len(), r.len());
+        LinearNonces { group, x, r }
+    }
+
+    /// Returns the nonces, to commit to.
+    pub(crate) fn nonces(&self) -> &[Secret<G::Scalar>] {
+        &self.r
+    }
+}
+
+impl<G: Group> SigmaNonces<G> for LinearNonces<'_, G> {
+    fn respond(self: Box<Self>, c: &G::Scalar) -> Vec<G::Scalar> {
+        let group = self.group;
+        self.r
+            .iter()
+            .zip(self.x)
+            .map(|(r, x)| {
+                let cx = Secret::new(group.scalar_mul(c, x.expose_secret()));
+                group.scalar_add(r.expose_secret(), cx.expose_secret())
+            })
+            .collect()
+    }
+}
+
+/// Checks that a statement has the `expected` number of public values.
+pub(crate) fn check_statement_length<T>(public: &[T], expected: usize) -> Result<(), VerifyError> {
+    if public.len() != expected {
+        return Err(VerifyError::StatementLength {
+            expected,
+            found: public.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that an instance has the `expected` number of commitments.
+pub(crate) fn check_commitment_count<T>(values: &[T], expected: usize) -> Result<(), VerifyError> {
+    if values.len() != expected {
+        return Err(VerifyError::CommitmentCount {
+            expected,
+            found: values.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that an instance has the `expected` number of responses.
+pub(crate) fn check_response_count<T>(values: &[T], expected: usize) -> Result<(), VerifyError> {
+    if values.len() != expected {
+        return Err(VerifyError::ResponseCount {
+            expected,
+            found: values.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the index of the first base that repeats an earlier one.
+pub(crate) fn duplicate_base<T: PartialEq>(bases: &[T]) -> Option<usize> {
+    (1..bases.len()).find(|&i| bases[..i].contains(&bases[i]))
+}
+
+/// Checks that every scalar of a transcript is canonical.
+pub(crate) fn check_scalars<'a, G: Group + 'a>(
+    group: &G,
+    scalars: impl IntoIterator<Item = &'a G::Scalar>,
+) -> Result<(), VerifyError> {
+    if !scalars.into_iter().all(|s| group.scalar_is_canonical(s)) {
+        return Err(VerifyError::NonCanonicalScalar);
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+//! The honest-verifier zero-knowledge simulator for the Schnorr protocol.
+//!
+//! A proof is zero-knowledge if everything the verifier sees could have been
+//! produced without the secret. [`simulate`] does exactly that: given only
+//! the public key `y` and a challenge `c`, it outputs an accepting transcript
+//! `(t, c, s)` whose distribution is identical to that of a real run with an
+//! honest verifier. A transcript therefore teaches the verifier nothing it
+//! could not have computed on its own.
+
+use rand::thread_rng;
+
+use crate::group::Group;
+
+/// Produces an accepting transcript `(t, c, s)` for the public key `y` and
+/// the challenge `c`, without knowing log_g(y).
+///
+/// Runs the protocol backwards: it picks the response `s` first and solves
+/// the verification equation for the commitment, `t = g^s * y^-c`. Real
+/// nonces are never zero, so real commitments are never the identity; `s`
+/// is resampled until `t` is not the identity either, which makes simulated
+/// and real transcripts identically distributed.
+pub fn simulate<G: Group>(
+    group: &G,
+    y: &G::Element,
+    c: &G::Scalar,
+) -> (G::Element, G::Scalar, G::Scalar) {
+    let mut rng = thread_rng();
+    let y_inv_c = group.invert(&group.exp(y, c));
+    loop {
+        let s = group.random_scalar(&mut rng);
+        let t = group.op(&group.exp(&group.generator(), &s), &y_inv_c);
+        if t != group.identity() {
+            return (t, c.clone(), s);
+        }
+    }
+}
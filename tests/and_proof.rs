@@ -0,0 +1,302 @@
+mod common;
+
+use num_bigint::BigUint;
+use sha2::Sha256;
+use sha3::Sha3_256;
+use zkp::and_proof::{challenge, Description, Statement};
+use zkp::group::Ristretto255;
+use zkp::sigma::SigmaProver;
+use zkp::{
+    AndProof, AndProver, AndVerifier, CompositionError, DecodeError, DleqProver, DleqVerifier,
+    Group, HashAlgorithm, OkamotoProver, OkamotoVerifier, OrProver, OrVerifier, PublicParams,
+    Verifier, VerifyError,
+};
+
+use common::{check_proof, check_session, random_prover};
+
+fn round_trip<G: Group + 'static>(
+    group: G,
+    provers: Vec<Box<dyn SigmaProver<G>>>,
+    statements: Vec<Statement<G>>,
+) {
+    let prover = AndProver::new(group.clone(), provers).unwrap();
+    let verifier = AndVerifier::new(group.clone(), statements).unwrap();
+    check_proof(
+        &prover.prove(b"context"),
+        |proof, context| verifier.verify_proof(proof, context),
+        |proof| proof.to_bytes(&group),
+        |bytes| AndProof::from_bytes(&group, bytes),
+    );
+    check_session(&prover, &verifier, &[]);
+}
+
+#[test]
+fn round_trip_of_schnorr_instances_over_ristretto255() {
+    let group = Ristretto255;
+    let first = random_prover(&group);
+    let y1 = *first.public_key();
+    let second = random_prover(&group);
+    let y2 = *second.public_key();
+    round_trip(
+        group,
+        vec![Box::new(first), Box::new(second)],
+        vec![
+            (Box::new(Verifier::new(group)), vec![y1]),
+            (Box::new(Verifier::new(group)), vec![y2]),
+        ],
+    );
+}
+
+#[test]
+fn round_trip_of_mixed_protocols() {
+    let params = PublicParams::rfc5114_2048_256();
+    let mut rng = rand::thread_rng();
+    let bases = params.independent_generators(3);
+    let h = bases[0].clone();
+
+    let schnorr = random_prover(&params);
+    let y = schnorr.public_key().clone();
+    let dleq = DleqProver::new(
+        params.clone(),
+        h.clone(),
+        params.random_nonzero_scalar(&mut rng),
+    )
+    .unwrap();
+    let (dleq_y, dleq_z) = (dleq.y().clone(), dleq.z().clone());
+    let secrets = (0..3).map(|_| params.random_scalar(&mut rng)).collect();
+    let okamoto = OkamotoProver::new(params.clone(), bases.clone(), secrets).unwrap();
+    let okamoto_y = okamoto.public_key().clone();
+
+    round_trip(
+        params.clone(),
+        vec![Box::new(schnorr), Box::new(dleq), Box::new(okamoto)],
+        vec![
+            (Box::new(Verifier::new(params.clone())), vec![y]),
+            (
+                Box::new(DleqVerifier::new(params.clone(), h).unwrap()),
+                vec![dleq_y, dleq_z],
+            ),
+            (
+                Box::new(OkamotoVerifier::new(params.clone(), bases).unwrap()),
+                vec![okamoto_y],
+            ),
+        ],
+    );
+}
+
+#[test]
+fn round_trip_with_an_or_statement() {
+    let group = Ristretto255;
+    let schnorr = random_prover(&group);
+    let y = *schnorr.public_key();
+    let other = *random_prover(&group).public_key();
+    let secret = group.random_nonzero_scalar(&mut rand::thread_rng());
+    let known = group.exp(&group.generator(), &secret);
+    let or = OrProver::new(group, vec![other, known], 1, secret).unwrap();
+
+    round_trip(
+        group,
+        vec![Box::new(schnorr), Box::new(or)],
+        vec![
+            (Box::new(Verifier::new(group)), vec![y]),
+            (Box::new(OrVerifier::new(group, vec![other, known])), vec![]),
+        ],
+    );
+}
+
+#[test]
+fn fails_if_any_statement_fails() {
+    let group = Ristretto255;
+    let first = random_prover(&group);
+    let y1 = *first.public_key();
+    let second = random_prover(&group);
+    let _ = *second.public_key();
+    let other = *random_prover(&group).public_key();
+    let prover = AndProver::new(group, vec![Box::new(first), Box::new(second)]).unwrap();
+    // The verifier expects a different second public key
+    let verifier = AndVerifier::new(
+        group,
+        vec![
+            (Box::new(Verifier::new(group)), vec![y1]),
+            (Box::new(Verifier::new(group)), vec![other]),
+        ],
+    )
+    .unwrap();
+
+    let proof = prover.prove(b"context");
+    assert_eq!(
+        verifier.verify_proof(&proof, b"context"),
+        Err(VerifyError::EquationFailed)
+    );
+
+    let committed = prover.commit();
+    let challenged = verifier
+        .receive_commitment(committed.commitments())
+        .unwrap()
+        .challenge();
+    let s = committed
+        .receive_challenge(*challenged.challenge())
+        .respond()
+        .responses()
+        .to_vec();
+    assert_eq!(
+        challenged.receive_response(s),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+#[test]
+fn rejects_malformed_statements_and_proofs() {
+    let group = Ristretto255;
+    let first = random_prover(&group);
+    let y1 = *first.public_key();
+    let prover = AndProver::new(group, vec![Box::new(first)]).unwrap();
+    let proof = prover.prove(b"context");
+
+    // A Schnorr statement has exactly one public value
+    let verifier =
+        AndVerifier::new(group, vec![(Box::new(Verifier::new(group)), vec![y1, y1])]).unwrap();
+    assert_eq!(
+        verifier.verify_proof(&proof, b"context"),
+        Err(VerifyError::StatementLength {
+            expected: 1,
+            found: 2
+        })
+    );
+
+    let verifier = AndVerifier::<_, Sha3_256>::with_hash(
+        group,
+        vec![(Box::new(Verifier::new(group)), vec![y1])],
+    )
+    .unwrap();
+    assert_eq!(
+        verifier.verify_proof(&proof, b"context"),
+        Err(VerifyError::HashMismatch {
+            expected: HashAlgorithm::Sha3_256,
+            found: HashAlgorithm::Sha256
+        })
+    );
+
+    assert_eq!(
+        AndProof::from_bytes(&group, &[1; 40]),
+        Err(DecodeError::WrongLength {
+            expected: 64,
+            actual: 39
+        })
+    );
+}
+
+#[test]
+fn rejects_responses_for_the_wrong_number_of_statements() {
+    let params = PublicParams::new();
+    let first = random_prover(&params);
+    let y1 = first.public_key().clone();
+    let second = random_prover(&params);
+    let y2 = second.public_key().clone();
+    let prover = AndProver::new(params.clone(), vec![Box::new(first), Box::new(second)]).unwrap();
+    let verifier = AndVerifier::new(
+        params.clone(),
+        vec![
+            (Box::new(Verifier::new(params.clone())), vec![y1]),
+            (Box::new(Verifier::new(params.clone())), vec![y2]),
+        ],
+    )
+    .unwrap();
+
+    let committed = prover.commit();
+    assert!(matches!(
+        verifier.receive_commitment(&committed.commitments()[..1]),
+        Err(VerifyError::CommitmentCount {
+            expected: 2,
+            found: 1
+        })
+    ));
+    let challenged = verifier
+        .receive_commitment(committed.commitments())
+        .unwrap()
+        .challenge();
+    let mut s = committed
+        .receive_challenge(challenged.challenge().clone())
+        .respond()
+        .responses()
+        .to_vec();
+    s.push(BigUint::from(1u32));
+    assert_eq!(
+        challenged.receive_response(s),
+        Err(VerifyError::ResponseCount {
+            expected: 2,
+            found: 3
+        })
+    );
+
+    let proof = prover.prove(b"context");
+    let mut short = proof.clone();
+    short.s.pop();
+    assert_eq!(
+        verifier.verify_proof(&short, b"context"),
+        Err(VerifyError::ResponseCount {
+            expected: 2,
+            found: 1
+        })
+    );
+    let mut unreduced = proof;
+    unreduced.s[1] += &params.q;
+    assert_eq!(
+        verifier.verify_proof(&unreduced, b"context"),
+        Err(VerifyError::NonCanonicalScalar)
+    );
+}
+
+#[test]
+fn rejects_statements_over_other_groups() {
+    let params = PublicParams::new();
+    let other = PublicParams::rfc5114_2048_256();
+    let first = random_prover(&params);
+    let y1 = first.public_key().clone();
+    let second = random_prover(&other);
+    let y2 = second.public_key().clone();
+
+    assert_eq!(
+        AndProver::new(params.clone(), vec![Box::new(first), Box::new(second)]).err(),
+        Some(CompositionError::GroupMismatch { index: 1 })
+    );
+    assert_eq!(
+        AndVerifier::new(
+            other.clone(),
+            vec![
+                (Box::new(Verifier::new(params.clone())), vec![y1]),
+                (Box::new(Verifier::new(other.clone())), vec![y2]),
+            ],
+        )
+        .err(),
+        Some(CompositionError::GroupMismatch { index: 0 })
+    );
+}
+
+#[test]
+fn rejects_empty_compositions() {
+    let group = Ristretto255;
+    assert_eq!(
+        AndProver::new(group, vec![]).err(),
+        Some(CompositionError::Empty)
+    );
+    assert_eq!(
+        AndVerifier::new(group, vec![]).err(),
+        Some(CompositionError::Empty)
+    );
+}
+
+#[test]
+fn challenge_binds_each_statement_protocol() {
+    let group = Ristretto255;
+    let y = *random_prover(&group).public_key();
+    let t = vec![vec![group.generator()]];
+    let c = |description: Description<Ristretto255>| {
+        challenge::<_, Sha256>(&group, &[description], &t, b"context")
+    };
+
+    assert_ne!(
+        c((b"zkp-other", vec![], vec![y])),
+        c((b"zkp-schnorr", vec![], vec![y]))
+    );
+}
@@ -0,0 +1,111 @@
+//! Helpers shared by the protocol tests.
+
+// Every test binary compiles this module but uses only part of it
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+use zkp::sigma::{self, SigmaProver, SigmaVerifier, VerifierCommitted};
+use zkp::{DecodeError, Group, Prover, VerifyError};
+
+/// Returns `n` random scalars in [1, q-1].
+pub fn random_scalars<G: Group>(group: &G, n: usize) -> Vec<G::Scalar> {
+    let mut rng = rand::thread_rng();
+    (0..n)
+        .map(|_| group.random_nonzero_scalar(&mut rng))
+        .collect()
+}
+
+/// Returns `g^x` for every `x` in `secrets`.
+pub fn public_keys<G: Group>(group: &G, secrets: &[G::Scalar]) -> Vec<G::Element> {
+    secrets
+        .iter()
+        .map(|x| group.exp(&group.generator(), x))
+        .collect()
+}
+
+/// Returns `n` random elements other than the identity.
+pub fn random_elements<G: Group>(group: &G, n: usize) -> Vec<G::Element> {
+    public_keys(group, &random_scalars(group, n))
+}
+
+/// Runs `prover` against `verifier` for the statement `public` through the
+/// generic sessions.
+///
+/// Checks that the honest run is accepted with the transcript the prover
+/// sent, that the transcript verifies on its own, and that responses to
+/// another challenge are rejected.
+pub fn check_session<G: Group>(
+    prover: &dyn SigmaProver<G>,
+    verifier: &dyn SigmaVerifier<G>,
+    public: &[G::Element],
+) {
+    let committed = prover.commit();
+    assert_eq!(committed.commitments().len(), verifier.commitment_count());
+    let challenged =
+        VerifierCommitted::new(verifier, public.to_vec(), committed.commitments().to_vec())
+            .unwrap()
+            .challenge();
+    let challenged_prover = committed.receive_challenge(challenged.challenge().clone());
+    assert_eq!(challenged_prover.challenge(), challenged.challenge());
+    let responded = challenged_prover.respond();
+    assert_eq!(responded.responses().len(), verifier.response_count());
+
+    let accepted = challenged
+        .receive_response(responded.responses().to_vec())
+        .unwrap();
+    assert_eq!(accepted, responded);
+    assert_eq!(
+        sigma::verify(
+            verifier,
+            public,
+            accepted.commitments(),
+            accepted.challenge(),
+            accepted.responses()
+        ),
+        Ok(())
+    );
+
+    let group = verifier.group();
+    let committed = prover.commit();
+    let challenged =
+        VerifierCommitted::new(verifier, public.to_vec(), committed.commitments().to_vec())
+            .unwrap()
+            .challenge();
+    let other = group.scalar_add(
+        challenged.challenge(),
+        &group.scalar_from_biguint(&1u32.into()),
+    );
+    let s = committed
+        .receive_challenge(other)
+        .respond()
+        .responses()
+        .to_vec();
+    assert_eq!(
+        challenged.receive_response(s),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+/// Checks that the non-interactive `proof` verifies for its context only and
+/// survives encoding.
+///
+/// `proof` must have been made for the context `b"context"`.
+pub fn check_proof<P: Clone + Debug + PartialEq>(
+    proof: &P,
+    verify: impl Fn(&P, &[u8]) -> Result<(), VerifyError>,
+    encode: impl Fn(&P) -> Vec<u8>,
+    decode: impl Fn(&[u8]) -> Result<P, DecodeError>,
+) {
+    assert_eq!(verify(proof, b"context"), Ok(()));
+    assert_eq!(
+        verify(proof, b"other context"),
+        Err(VerifyError::EquationFailed)
+    );
+    assert_eq!(decode(&encode(proof)).as_ref(), Ok(proof));
+}
+
+/// Returns a Schnorr prover for a random secret.
+pub fn random_prover<G: Group>(group: &G) -> Prover<G> {
+    Prover::new(group.clone(), random_scalars(group, 1).remove(0)).unwrap()
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use num_bigint::BigUint;
+use zkp::group::Ristretto255;
+use zkp::{
+    DecodeError, DleqProof, DleqProver, DleqVerifier, ElementError, Group, ProverError,
+    PublicParams, VerifyError,
+};
+
+use common::{check_proof, check_session, random_elements, random_scalars};
+
+fn round_trip<G: Group>(group: G) {
+    let h = random_elements(&group, 1).remove(0);
+    let x = random_scalars(&group, 1).remove(0);
+    let prover = DleqProver::new(group.clone(), h.clone(), x).unwrap();
+    let verifier = DleqVerifier::new(group.clone(), h).unwrap();
+    let (y, z) = (prover.y(), prover.z());
+
+    check_proof(
+        &prover.prove(b"context"),
+        |proof, context| verifier.verify_proof(y, z, proof, context),
+        |proof| proof.to_bytes(&group),
+        |bytes| DleqProof::from_bytes(&group, bytes),
+    );
+    check_session(&prover, &verifier, &[y.clone(), z.clone()]);
+}
+
+#[test]
+fn round_trip_over_rfc5114_group_and_ristretto255() {
+    round_trip(PublicParams::rfc5114_2048_256());
+    round_trip(Ristretto255);
+}
+
+#[test]
+fn rejects_another_z() {
+    let group = Ristretto255;
+    let h = random_elements(&group, 1).remove(0);
+    let prover = DleqProver::new(group, h, random_scalars(&group, 1).remove(0)).unwrap();
+    let verifier = DleqVerifier::new(group, h).unwrap();
+
+    // z = h^x' for another exponent x'
+    let wrong_z = group.exp(&h, &random_scalars(&group, 1)[0]);
+    let proof = prover.prove(b"context");
+    assert_eq!(
+        verifier.verify_proof(prover.y(), &wrong_z, &proof, b"context"),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+#[test]
+fn rejects_unreduced_responses() {
+    let group = PublicParams::new();
+    let h = BigUint::from(9u32);
+    let prover = DleqProver::new(group.clone(), h.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = DleqVerifier::new(group.clone(), h).unwrap();
+
+    let mut proof = prover.prove(b"context");
+    proof.s += &group.q;
+    assert_eq!(
+        verifier.verify_proof(prover.y(), prover.z(), &proof, b"context"),
+        Err(VerifyError::NonCanonicalScalar)
+    );
+}
+
+#[test]
+fn cannot_prove_unequal_logarithms() {
+    let group = PublicParams::new();
+    let h = BigUint::from(9u32);
+    let prover = DleqProver::new(group.clone(), h.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = DleqVerifier::new(group, h).unwrap();
+
+    // 4^7 = 8 (mod 23), while the prover knows 9^6 = 3
+    let z = BigUint::from(8u32);
+    let committed = prover.commit();
+    let [a, b] = committed.commitments() else {
+        unreachable!()
+    };
+    let challenged = verifier
+        .receive_commitment(prover.y(), &z, a, b)
+        .unwrap()
+        .challenge();
+    let c = challenged.challenge().clone();
+    let s = committed
+        .receive_challenge(c.clone())
+        .respond()
+        .responses()
+        .to_vec();
+    // Only the zero challenge, drawn with probability 1/q, lets a cheater pass
+    if c != BigUint::from(0u32) {
+        assert_eq!(
+            challenged.receive_response(s),
+            Err(VerifyError::EquationFailed)
+        );
+    }
+}
+
+#[test]
+fn rejects_invalid_bases() {
+    let group = PublicParams::new();
+    let x = BigUint::from(6u32);
+    // 22 = -1 mod 23 has order 2, 23 = p is out of range, and with h = 1
+    // every z = 1 would pass
+    for (h, error) in [
+        (22u32, ElementError::NotInSubgroup),
+        (23, ElementError::OutOfRange),
+        (1, ElementError::Identity),
+    ] {
+        let h = BigUint::from(h);
+        assert_eq!(
+            DleqProver::new(group.clone(), h.clone(), x.clone()).err(),
+            Some(ProverError::InvalidBase(error))
+        );
+        assert!(matches!(
+            DleqVerifier::new(group.clone(), h),
+            Err(VerifyError::InvalidBase(e)) if e == error
+        ));
+    }
+
+    let group = Ristretto255;
+    let x = group.random_nonzero_scalar(&mut rand::thread_rng());
+    assert_eq!(
+        DleqProver::new(group, group.identity(), x).err(),
+        Some(ProverError::InvalidBase(ElementError::Identity))
+    );
+    assert!(matches!(
+        DleqVerifier::new(group, group.identity()),
+        Err(VerifyError::InvalidBase(ElementError::Identity))
+    ));
+}
+
+#[test]
+fn rejects_proofs_of_the_wrong_length() {
+    let group = Ristretto255;
+    assert_eq!(
+        DleqProof::from_bytes(&group, &[1; 97]),
+        Err(DecodeError::WrongLength {
+            expected: 64,
+            actual: 96
+        })
+    );
+    assert_eq!(
+        DleqProof::from_bytes(&group, &[1; 33]),
+        Err(DecodeError::WrongLength {
+            expected: 64,
+            actual: 32
+        })
+    );
+}
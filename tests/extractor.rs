@@ -0,0 +1,75 @@
+use num_bigint::BigUint;
+use zkp::extractor::{extract, rewind};
+use zkp::group::Ristretto255;
+use zkp::simulator::simulate;
+use zkp::{ExtractionError, Group, Prover, PublicParams, VerifyError};
+
+#[test]
+fn rewinding_recovers_the_secret() {
+    let group = PublicParams::new();
+    for x in 1u32..11 {
+        let prover = Prover::new(group.clone(), BigUint::from(x)).unwrap();
+        let extracted = rewind(&prover).unwrap();
+        assert_eq!(extracted.expose_secret(), &BigUint::from(x));
+    }
+
+    let group = PublicParams::rfc5114_2048_256();
+    let x = BigUint::from(0x1234_5678_9abc_def0_u64);
+    let prover = Prover::new(group, x.clone()).unwrap();
+    assert_eq!(rewind(&prover).unwrap().expose_secret(), &x);
+
+    let group = Ristretto255;
+    let x = group.random_nonzero_scalar(&mut rand::thread_rng());
+    let prover = Prover::new(group, x).unwrap();
+    assert_eq!(rewind(&prover).unwrap().expose_secret(), &x);
+}
+
+#[test]
+fn extract_requires_a_shared_commitment_and_distinct_challenges() {
+    let group = PublicParams::new();
+    let prover = Prover::new(group.clone(), BigUint::from(6u32)).unwrap();
+    let y = prover.public_key();
+
+    let c = BigUint::from(3u32);
+    let (t1, c1, s1) = simulate(&group, y, &c);
+    let (t2, c2, s2) = loop {
+        let transcript = simulate(&group, y, &c);
+        if transcript.0 != t1 {
+            break transcript;
+        }
+    };
+    assert_eq!(
+        extract(&group, y, (&t1, &c1, &s1), (&t2, &c2, &s2)).err(),
+        Some(ExtractionError::DifferentCommitments)
+    );
+    assert_eq!(
+        extract(&group, y, (&t1, &c1, &s1), (&t1, &c1, &s1)).err(),
+        Some(ExtractionError::EqualChallenges)
+    );
+    let s_bad = (&s1 + 1u32) % 11u32;
+    assert_eq!(
+        extract(&group, y, (&t1, &c1, &s_bad), (&t2, &c2, &s2)).err(),
+        Some(ExtractionError::InvalidTranscript(
+            VerifyError::EquationFailed
+        ))
+    );
+}
+
+#[test]
+fn extract_rejects_congruent_challenges() {
+    // Over the toy group (q = 11) with x = 6, t = g^2 answers c = 3 and its
+    // unreduced representative c = 14 with the same s = 9; the unreduced
+    // transcript is rejected before the challenges are compared
+    let group = PublicParams::new();
+    let g = group.generator();
+    let y = group.exp(&g, &BigUint::from(6u32));
+    let t = group.exp(&g, &BigUint::from(2u32));
+    let s = BigUint::from(9u32);
+    let (c1, c2) = (BigUint::from(3u32), BigUint::from(14u32));
+    assert_eq!(
+        extract(&group, &y, (&t, &c1, &s), (&t, &c2, &s)).err(),
+        Some(ExtractionError::InvalidTranscript(
+            VerifyError::NonCanonicalScalar
+        ))
+    );
+}
@@ -0,0 +1,147 @@
+use num_bigint::BigUint;
+use zkp::fips186::canonical_generator;
+use zkp::{ParamsError, Provenance, PublicParams};
+
+/// (L, N) = (1024, 160) parameters derived with SHA-256 per FIPS 186-4
+/// A.1.1.2 and A.2.3 by an independent implementation of the standard.
+const SEED: &str = "1fa1d4fcb1cdc0c62059f0ee7c1651640217a6ad";
+const COUNTER: u32 = 89;
+const Q: &str = "f2a0af01507d20389d5d696e4b6d34f2a43e7435";
+const P: &str = "91136187db9eef6402a0b108e62166a52f35f6d17956aac8413d2f973c13059b\
+                 64b6f7bba1d01cf4c8252fa30f667d9da0e8faffca886b002f08fd8b908b07cb\
+                 e8ef862cf3180383d7e3fa7de88ae58bfaab7b9eee0cf9c884b7fe1b232da301\
+                 00dfe208ab3ea5392323a634836e234006b2c44f4b085112fe7b0823ef9dbbf5";
+/// The generator for index 1.
+const G1: &str = "5f98e1eb126e268b275762db0f9ff5f032acb95b28f96efee6fcebe2e060cb69\
+                  241248b0c8068569c0cae2cfc53a8add03fc92d28c772e4f2b6baadfa4fa3ca2\
+                  081af3b9938aeb107f119c8ea7397b7693acc573a5b60af428475b1f83a2df33\
+                  8e9253f7b022f602370594c661d964ee7825676e923e80d1d479fe10dea2f409";
+/// The generator for index 2.
+const G2: &str = "6f3b675760d1300d3f8a615635d62fd927f7e49fc5efeb5161c922fd299fb36b\
+                  ca0ec5f24da5f18bf6ff0ef57c7e9938e5f625fcc50e0cf9f0278ae1abbdc7e3\
+                  b0cdf5c835e1d2d22cca5aff17b0476f88df749f200bfc767d768bc34624f8c0\
+                  3e36d80ae6ce851a8de6f9da7d7ccc01e76cec7db2159369d3bc5fdb8d3e1ab8";
+
+fn int(hex: &str) -> BigUint {
+    BigUint::parse_bytes(hex.as_bytes(), 16).unwrap()
+}
+
+fn known_answer() -> PublicParams {
+    PublicParams {
+        p: int(P),
+        q: int(Q),
+        g: int(G1),
+        provenance: Some(Provenance {
+            domain_parameter_seed: hex::decode(SEED).unwrap(),
+            counter: COUNTER,
+            index: 1,
+        }),
+    }
+}
+
+#[test]
+fn known_answer_verifies() {
+    let params = known_answer();
+    assert_eq!(params.verify_provenance(), Ok(()));
+    assert_eq!(params.validate(), Ok(()));
+
+    let seed = hex::decode(SEED).unwrap();
+    assert_eq!(
+        canonical_generator(&params.p, &params.q, &seed, 1),
+        Some(int(G1))
+    );
+    assert_eq!(
+        canonical_generator(&params.p, &params.q, &seed, 2),
+        Some(int(G2))
+    );
+}
+
+#[test]
+fn generated_params_verify() {
+    let params = PublicParams::generate_fips186(1024, 160, &mut rand::thread_rng());
+    assert_eq!(params.p.bits(), 1024);
+    assert_eq!(params.q.bits(), 160);
+    let provenance = params.provenance.as_ref().unwrap();
+    assert_eq!(provenance.domain_parameter_seed.len(), 20);
+    assert_eq!(provenance.index, 1);
+    assert_eq!(params.verify_provenance(), Ok(()));
+    assert_eq!(params.validate(), Ok(()));
+}
+
+#[test]
+fn rejects_tampered_seed() {
+    let mut params = known_answer();
+    params.provenance.as_mut().unwrap().domain_parameter_seed[0] ^= 1;
+    assert_eq!(
+        params.verify_provenance(),
+        Err(ParamsError::PrimesNotFromSeed)
+    );
+
+    let mut params = known_answer();
+    params
+        .provenance
+        .as_mut()
+        .unwrap()
+        .domain_parameter_seed
+        .pop();
+    assert_eq!(
+        params.verify_provenance(),
+        Err(ParamsError::PrimesNotFromSeed)
+    );
+}
+
+#[test]
+fn rejects_tampered_counter() {
+    for counter in [COUNTER - 1, COUNTER + 1, 4 * 1024] {
+        let mut params = known_answer();
+        params.provenance.as_mut().unwrap().counter = counter;
+        assert_eq!(
+            params.verify_provenance(),
+            Err(ParamsError::PrimesNotFromSeed),
+            "counter {counter}"
+        );
+    }
+}
+
+#[test]
+fn rejects_tampered_index() {
+    let mut params = known_answer();
+    params.provenance.as_mut().unwrap().index = 2;
+    assert_eq!(
+        params.verify_provenance(),
+        Err(ParamsError::GeneratorNotFromSeed)
+    );
+}
+
+#[test]
+fn rejects_tampered_generator() {
+    // The index-2 generator is a valid generator, just not the one claimed
+    for g in [int(G2), BigUint::from(1u32), int(P)] {
+        let mut params = known_answer();
+        params.g = g;
+        assert_eq!(
+            params.verify_provenance(),
+            Err(ParamsError::GeneratorNotFromSeed)
+        );
+    }
+}
+
+#[test]
+fn rejects_missing_provenance() {
+    let mut params = known_answer();
+    params.provenance = None;
+    assert_eq!(
+        params.verify_provenance(),
+        Err(ParamsError::MissingProvenance)
+    );
+    assert_eq!(
+        PublicParams::rfc5114_2048_256().verify_provenance(),
+        Err(ParamsError::MissingProvenance)
+    );
+}
+
+#[test]
+#[should_panic(expected = "is not approved by FIPS 186-4")]
+fn generate_rejects_unapproved_sizes() {
+    PublicParams::generate_fips186(1024, 256, &mut rand::thread_rng());
+}
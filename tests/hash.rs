@@ -0,0 +1,96 @@
+use blake2::Blake2b512;
+use sha2::{Sha256, Sha512};
+use sha3::Sha3_256;
+use zkp::group::{Group, Ristretto255};
+use zkp::hash::{expand_message_xmd, hash_to_scalar};
+use zkp::schnorr::challenge;
+use zkp::{ChallengeHash, Prover, PublicParams, Verifier};
+
+const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+
+/// expand_message_xmd(SHA-256) vectors from RFC 9380, appendix K.1.
+const VECTORS: [(&[u8], usize, &str); 4] = [
+    (
+        b"",
+        0x20,
+        "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+    ),
+    (
+        b"abc",
+        0x20,
+        "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+    ),
+    (
+        b"",
+        0x80,
+        "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+         e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+         eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+         c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+    ),
+    (
+        b"abc",
+        0x80,
+        "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+         647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+         bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+         058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+    ),
+];
+
+#[test]
+fn expand_message_xmd_matches_rfc9380() {
+    for (msg, len, expected) in VECTORS {
+        let out = expand_message_xmd::<Sha256>(msg, DST, len);
+        assert_eq!(hex::encode(out), expected);
+    }
+}
+
+#[test]
+fn hash_to_scalar_is_uniform_over_toy_group() {
+    let group = PublicParams::new();
+    let mut counts = [0u32; 11];
+    for i in 0u32..11000 {
+        let c = hash_to_scalar::<_, Sha256>(&group, &i.to_be_bytes(), b"test");
+        let c: usize = group.scalar_to_biguint(&c).try_into().unwrap();
+        counts[c] += 1;
+    }
+    // Each value is expected 1000 times
+    for (c, &count) in counts.iter().enumerate() {
+        assert!(
+            (850..1150).contains(&count),
+            "c = {c} produced {count} times"
+        );
+    }
+}
+
+fn run_with_hash<H: ChallengeHash>() -> curve25519_dalek::Scalar {
+    let group = Ristretto255;
+    let secret = group.random_nonzero_scalar(&mut rand::thread_rng());
+    let prover = Prover::<_, H>::with_hash(group, secret).unwrap();
+    let verifier = Verifier::<_, H>::with_hash(group);
+
+    let proof = prover.prove(b"test");
+    assert_eq!(
+        verifier.verify_proof(prover.public_key(), &proof, b"test"),
+        Ok(())
+    );
+
+    let g = group.generator();
+    challenge::<_, H>(&group, &g, &g, b"test")
+}
+
+#[test]
+fn challenges_depend_on_the_hash_function() {
+    let challenges = [
+        run_with_hash::<Sha256>(),
+        run_with_hash::<Sha512>(),
+        run_with_hash::<Sha3_256>(),
+        run_with_hash::<Blake2b512>(),
+    ];
+    for i in 0..challenges.len() {
+        for j in i + 1..challenges.len() {
+            assert_ne!(challenges[i], challenges[j]);
+        }
+    }
+}
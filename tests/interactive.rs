@@ -0,0 +1,119 @@
+use num_bigint::BigUint;
+use zkp::{Prover, PublicParams, Verifier, VerifyError};
+
+#[test]
+fn honest_prover_is_accepted() {
+    let params = PublicParams::new();
+    let prover = Prover::new(params.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(params);
+
+    for _ in 0..100 {
+        let committed = prover.commit();
+        let challenged = verifier
+            .receive_commitment(prover.public_key(), &committed.commitments()[0])
+            .unwrap()
+            .challenge();
+        let responded = committed
+            .receive_challenge(challenged.challenge().clone())
+            .respond();
+        let accepted = challenged
+            .receive_response(responded.responses().to_vec())
+            .unwrap();
+        assert_eq!(&accepted.commitments()[0], &responded.commitments()[0]);
+        assert_eq!(accepted.challenge(), responded.challenge());
+    }
+}
+
+#[test]
+fn challenges_are_uniform_and_independent_of_the_commitment() {
+    let params = PublicParams::new();
+    let prover = Prover::new(params.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(params);
+
+    // The same commitment is challenged repeatedly
+    let committed = prover.commit();
+    let mut counts = [0u32; 11];
+    for _ in 0..11000 {
+        let challenged = verifier
+            .receive_commitment(prover.public_key(), &committed.commitments()[0])
+            .unwrap()
+            .challenge();
+        let c: usize = challenged.challenge().clone().try_into().unwrap();
+        counts[c] += 1;
+    }
+    // Each value is expected 1000 times
+    for (c, &count) in counts.iter().enumerate() {
+        assert!(
+            (850..1150).contains(&count),
+            "c = {c} produced {count} times"
+        );
+    }
+}
+
+#[test]
+fn response_to_another_challenge_is_rejected() {
+    let params = PublicParams::new();
+    let prover = Prover::new(params.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(params);
+
+    let committed = prover.commit();
+    let challenged = verifier
+        .receive_commitment(prover.public_key(), &committed.commitments()[0])
+        .unwrap()
+        .challenge();
+    let c = (challenged.challenge() + 1u32) % 11u32;
+    let responded = committed.receive_challenge(c).respond();
+    assert_eq!(
+        challenged.receive_response(responded.responses().to_vec()),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+#[test]
+fn commitments_outside_the_subgroup_are_rejected() {
+    let params = PublicParams::new();
+    let prover = Prover::new(params.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(params);
+
+    // 5 has order 22 in Z_23*
+    assert!(matches!(
+        verifier.receive_commitment(prover.public_key(), &BigUint::from(5u32)),
+        Err(VerifyError::InvalidCommitment(_))
+    ));
+}
+
+#[test]
+fn unreduced_scalars_are_rejected() {
+    let params = PublicParams::new();
+    let prover = Prover::new(params.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(params.clone());
+    let y = prover.public_key();
+
+    // (c, s + q) and (c + q, s) satisfy the equation, but are other
+    // encodings of the same transcript
+    let proof = prover.prove(b"context");
+    let mut malleated = proof.clone();
+    malleated.s += &params.q;
+    assert_eq!(
+        verifier.verify_proof(y, &malleated, b"context"),
+        Err(VerifyError::NonCanonicalScalar)
+    );
+    let t = proof.commitment(&params, y);
+    assert_eq!(verifier.verify(&t, &proof.c, &proof.s, y), Ok(()));
+    assert_eq!(
+        verifier.verify(&t, &(&proof.c + &params.q), &proof.s, y),
+        Err(VerifyError::NonCanonicalScalar)
+    );
+
+    let committed = prover.commit();
+    let challenged = verifier
+        .receive_commitment(y, &committed.commitments()[0])
+        .unwrap()
+        .challenge();
+    let c = challenged.challenge().clone();
+    let s = &committed.receive_challenge(c).respond().responses()[0] + &params.q;
+    assert_eq!(
+        challenged.receive_response(vec![s]),
+        Err(VerifyError::NonCanonicalScalar)
+    );
+}
@@ -0,0 +1,136 @@
+use num_bigint::BigUint;
+use zkp::group::Group;
+use zkp::{DecodeError, ElementError, PublicParams};
+
+#[test]
+fn elements_round_trip() {
+    // Every non-identity element of the toy group
+    let params = PublicParams::new();
+    for k in 1u32..11 {
+        let element = params.exp(&params.generator(), &BigUint::from(k));
+        let encoded = params.encode_element(&element);
+        assert_eq!(encoded.len(), 1);
+        assert_eq!(params.decode_element(&encoded), Ok(element));
+    }
+
+    let params = PublicParams::rfc5114_2048_256();
+    let mut rng = rand::thread_rng();
+    for _ in 0..8 {
+        let k = params.random_nonzero_scalar(&mut rng);
+        let element = params.exp(&params.generator(), &k);
+        let encoded = params.encode_element(&element);
+        assert_eq!(encoded.len(), 256);
+        assert_eq!(params.decode_element(&encoded), Ok(element));
+    }
+}
+
+#[test]
+fn short_elements_are_padded() {
+    let params = PublicParams::rfc5114_2048_256();
+    let encoded = params.encode_element(&BigUint::from(0x0102u32));
+    assert_eq!(encoded.len(), 256);
+    assert!(encoded[..254].iter().all(|&b| b == 0));
+    assert_eq!(encoded[254..], [0x01, 0x02]);
+}
+
+#[test]
+fn decode_rejects_out_of_range_and_identity_elements() {
+    let params = PublicParams::rfc5114_2048_256();
+    let len = 256;
+    let p = params.p.to_bytes_be();
+    let p_plus_one = (&params.p + 1u32).to_bytes_be();
+    for bytes in [vec![0u8; len], p, p_plus_one, vec![0xff; len]] {
+        assert_eq!(params.decode_element(&bytes), Err(DecodeError::OutOfRange));
+    }
+
+    let mut one = vec![0u8; len];
+    one[len - 1] = 1;
+    assert_eq!(params.decode_element(&one), Err(DecodeError::Identity));
+
+    for bytes in [&one[1..], &[one.as_slice(), &[0]].concat()[..], &[]] {
+        assert_eq!(
+            params.decode_element(bytes),
+            Err(DecodeError::WrongLength {
+                expected: len,
+                actual: bytes.len()
+            })
+        );
+    }
+}
+
+#[test]
+fn check_element_rejects_non_members() {
+    let params = PublicParams::new();
+    for element in [0u32, 23, 24] {
+        assert_eq!(
+            params.check_element(&BigUint::from(element)),
+            Err(ElementError::OutOfRange)
+        );
+    }
+    // 5 generates all of Z_23*, and 22 = -1 has order 2
+    for element in [5u32, 22] {
+        assert_eq!(
+            params.check_element(&BigUint::from(element)),
+            Err(ElementError::NotInSubgroup)
+        );
+    }
+    assert_eq!(
+        params.check_element(&BigUint::from(1u32)),
+        Err(ElementError::Identity)
+    );
+    for element in [4u32, 2] {
+        assert_eq!(params.check_element(&BigUint::from(element)), Ok(()));
+    }
+}
+
+#[test]
+fn scalars_round_trip_and_reject_values_not_below_q() {
+    let params = PublicParams::rfc5114_2048_256();
+    let q = params.q.clone();
+
+    let one = params.encode_scalar(&BigUint::from(1u32));
+    assert_eq!(one.len(), 32);
+    assert_eq!(one[31], 1);
+    assert!(one[..31].iter().all(|&b| b == 0));
+
+    let q_minus_one = &q - 1u32;
+    let encoded = params.encode_scalar(&q_minus_one);
+    assert_eq!(params.decode_scalar(&encoded), Ok(q_minus_one));
+
+    for n in [q.clone(), &q + 1u32] {
+        assert_eq!(
+            params.decode_scalar(&n.to_bytes_be()),
+            Err(DecodeError::OutOfRange)
+        );
+    }
+    assert_eq!(
+        params.decode_scalar(&[0xff; 32]),
+        Err(DecodeError::OutOfRange)
+    );
+    assert_eq!(
+        params.decode_scalar(&[0; 31]),
+        Err(DecodeError::WrongLength {
+            expected: 32,
+            actual: 31
+        })
+    );
+}
+
+#[test]
+fn group_operations_match_modular_arithmetic() {
+    let params = PublicParams::new();
+    let g = params.generator();
+    assert_eq!(params.identity(), BigUint::from(1u32));
+    assert_eq!(params.order(), BigUint::from(11u32));
+    // 4 * 16 = 64 = 18 (mod 23)
+    assert_eq!(params.op(&g, &BigUint::from(16u32)), BigUint::from(18u32));
+    for k in 1u32..11 {
+        let element = params.exp(&g, &BigUint::from(k));
+        assert_eq!(
+            params.op(&element, &params.invert(&element)),
+            params.identity()
+        );
+    }
+    // Exponents wrap around modulo q
+    assert_eq!(params.exp(&g, &BigUint::from(11u32)), params.identity());
+}
@@ -0,0 +1,71 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use zkp::primes::is_probable_prime;
+use zkp::PublicParams;
+
+/// Miller-Rabin rounds for the constants; they are public and well known,
+/// so this only guards against typos.
+const ROUNDS: usize = 4;
+
+fn check_group(name: &str, params: PublicParams, p_bits: u64, q_bits: u64) {
+    let mut rng = rand::thread_rng();
+    let PublicParams { p, q, g, .. } = params;
+
+    assert_eq!(p.bits(), p_bits, "{name}: p has the wrong size");
+    assert_eq!(q.bits(), q_bits, "{name}: q has the wrong size");
+    assert!(
+        is_probable_prime(&p, ROUNDS, &mut rng),
+        "{name}: p is not prime"
+    );
+    assert!(
+        is_probable_prime(&q, ROUNDS, &mut rng),
+        "{name}: q is not prime"
+    );
+    assert!(
+        ((&p - 1u32) % &q).is_zero(),
+        "{name}: q does not divide p-1"
+    );
+    assert!(g > BigUint::one() && g < p, "{name}: g is out of range");
+    assert!(g.modpow(&q, &p).is_one(), "{name}: g does not have order q");
+}
+
+#[test]
+fn rfc3526_modp_groups() {
+    check_group("modp_1536", PublicParams::modp_1536(), 1536, 1535);
+    check_group("modp_2048", PublicParams::modp_2048(), 2048, 2047);
+    check_group("modp_3072", PublicParams::modp_3072(), 3072, 3071);
+    check_group("modp_4096", PublicParams::modp_4096(), 4096, 4095);
+    check_group("modp_6144", PublicParams::modp_6144(), 6144, 6143);
+    check_group("modp_8192", PublicParams::modp_8192(), 8192, 8191);
+}
+
+#[test]
+fn rfc7919_ffdhe_groups() {
+    check_group("ffdhe2048", PublicParams::ffdhe2048(), 2048, 2047);
+    check_group("ffdhe3072", PublicParams::ffdhe3072(), 3072, 3071);
+    check_group("ffdhe4096", PublicParams::ffdhe4096(), 4096, 4095);
+    check_group("ffdhe6144", PublicParams::ffdhe6144(), 6144, 6143);
+    check_group("ffdhe8192", PublicParams::ffdhe8192(), 8192, 8191);
+}
+
+#[test]
+fn rfc5114_groups() {
+    check_group(
+        "rfc5114_1024_160",
+        PublicParams::rfc5114_1024_160(),
+        1024,
+        160,
+    );
+    check_group(
+        "rfc5114_2048_224",
+        PublicParams::rfc5114_2048_224(),
+        2048,
+        224,
+    );
+    check_group(
+        "rfc5114_2048_256",
+        PublicParams::rfc5114_2048_256(),
+        2048,
+        256,
+    );
+}
@@ -0,0 +1,62 @@
+use num_bigint::BigUint;
+use sha2::{Sha256, Sha512};
+use zkp::group::{Group, P256};
+use zkp::nonce::rfc6979_nonce;
+use zkp::{ChallengeHash, Prover, PublicParams, Verifier};
+
+/// The P-256 private key from RFC 6979, appendix A.2.5.
+const P256_X: &str = "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721";
+
+fn p256_nonce<H: ChallengeHash>(message: &[u8]) -> String {
+    let group = P256;
+    let x = group.scalar_from_biguint(&BigUint::parse_bytes(P256_X.as_bytes(), 16).unwrap());
+    let h1 = H::digest(message);
+    let k = rfc6979_nonce::<_, H>(&group, &x, &h1, &[]);
+    format!("{:064X}", group.scalar_to_biguint(k.expose_secret()))
+}
+
+#[test]
+fn matches_rfc6979_p256_vectors() {
+    assert_eq!(
+        p256_nonce::<Sha256>(b"sample"),
+        "A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60"
+    );
+    assert_eq!(
+        p256_nonce::<Sha256>(b"test"),
+        "D16B6AE827F17175E040871A1C7EC3500192C4C92677336EC2537ACAEE0008E0"
+    );
+    assert_eq!(
+        p256_nonce::<Sha512>(b"sample"),
+        "5FA81C63109BADB88C1F367B47DA606DA28CAD69AA22C4FE6AD7DF73A7173AA5"
+    );
+}
+
+#[test]
+fn deterministic_proofs_depend_only_on_secret_and_statement() {
+    let params = PublicParams::rfc5114_2048_256();
+    let prover = Prover::new(params.clone(), BigUint::from(123456789u32)).unwrap();
+    let verifier = Verifier::new(params);
+
+    let proof = prover.prove_deterministic(b"context");
+    assert_eq!(prover.prove_deterministic(b"context"), proof);
+    assert_ne!(prover.prove_deterministic(b"other context"), proof);
+    assert_eq!(
+        verifier.verify_proof(prover.public_key(), &proof, b"context"),
+        Ok(())
+    );
+}
+
+#[test]
+fn hedged_proofs_are_randomized() {
+    let group = P256;
+    let secret = group.random_nonzero_scalar(&mut rand::thread_rng());
+    let prover = Prover::new(group, secret).unwrap();
+    let verifier = Verifier::new(group);
+
+    let proof = prover.prove_hedged(b"context");
+    assert_ne!(prover.prove_hedged(b"context"), proof);
+    assert_eq!(
+        verifier.verify_proof(prover.public_key(), &proof, b"context"),
+        Ok(())
+    );
+}
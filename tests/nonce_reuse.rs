@@ -0,0 +1,197 @@
+use num_bigint::BigUint;
+use sha2::Sha256;
+use zkp::attack::recover_secret;
+use zkp::group::Secp256k1;
+use zkp::schnorr::challenge;
+use zkp::{
+    DetectorError, ElementError, ExtractionError, Group, HashAlgorithm, NonceReuseDetector, Prover,
+    PublicParams, SchnorrProof, Verifier, VerifyError, ZeroCapacityError,
+};
+
+/// Proves knowledge of `x` with the nonce `r`, like a prover whose random
+/// number generator repeats.
+fn proof_with_nonce<G: Group>(
+    group: &G,
+    x: &G::Scalar,
+    r: &G::Scalar,
+    context: &[u8],
+) -> SchnorrProof<G> {
+    let y = group.exp(&group.generator(), x);
+    let t = group.exp(&group.generator(), r);
+    let c = challenge::<_, Sha256>(group, &y, &t, context);
+    let s = group.scalar_add(r, &group.scalar_mul(&c, x));
+    SchnorrProof {
+        c,
+        s,
+        hash: HashAlgorithm::Sha256,
+    }
+}
+
+fn check_attack<G: Group>(group: G) {
+    let mut rng = rand::thread_rng();
+    let x = group.random_nonzero_scalar(&mut rng);
+    let r = group.random_nonzero_scalar(&mut rng);
+    let y = group.exp(&group.generator(), &x);
+
+    let first = proof_with_nonce(&group, &x, &r, b"first message");
+    let second = proof_with_nonce(&group, &x, &r, b"second message");
+    let verifier = Verifier::new(group.clone());
+    assert_eq!(verifier.verify_proof(&y, &first, b"first message"), Ok(()));
+    assert_eq!(
+        verifier.verify_proof(&y, &second, b"second message"),
+        Ok(())
+    );
+
+    let recovered = recover_secret(&group, &y, &first, &second).unwrap();
+    assert_eq!(recovered.expose_secret(), &x);
+}
+
+#[test]
+fn reused_nonce_reveals_the_secret() {
+    check_attack(PublicParams::rfc5114_2048_256());
+    check_attack(Secp256k1);
+}
+
+#[test]
+fn honest_proofs_do_not_reveal_the_secret() {
+    let group = PublicParams::rfc5114_2048_256();
+    let prover = Prover::new(group.clone(), BigUint::from(123456789u32)).unwrap();
+    let first = prover.prove(b"first message");
+    let second = prover.prove(b"second message");
+    assert_eq!(
+        recover_secret(&group, prover.public_key(), &first, &second).err(),
+        Some(ExtractionError::DifferentCommitments)
+    );
+}
+
+#[test]
+fn congruent_challenges_do_not_reveal_the_secret() {
+    // Over the toy group (q = 11) with x = 6, both proofs recompute t = g^2,
+    // but the one with the unreduced challenge is not a valid proof
+    let group = PublicParams::new();
+    let y = group.exp(&group.generator(), &BigUint::from(6u32));
+    let proof = |c: u32| SchnorrProof::<PublicParams> {
+        c: BigUint::from(c),
+        s: BigUint::from(9u32),
+        hash: HashAlgorithm::Sha256,
+    };
+    assert_eq!(
+        recover_secret(&group, &y, &proof(3), &proof(14)).err(),
+        Some(ExtractionError::InvalidTranscript(
+            VerifyError::NonCanonicalScalar
+        ))
+    );
+}
+
+#[test]
+fn detector_flags_repeated_commitments() {
+    let group = Secp256k1;
+    let mut rng = rand::thread_rng();
+    let x = group.random_nonzero_scalar(&mut rng);
+    let r = group.random_nonzero_scalar(&mut rng);
+    let y = group.exp(&group.generator(), &x);
+    let prover = Prover::new(group, x).unwrap();
+
+    let mut detector = NonceReuseDetector::new(16).unwrap();
+    for i in 0u32..10 {
+        let proof = prover.prove(&i.to_be_bytes());
+        assert_eq!(detector.check_proof(&group, &y, &proof), Ok(()));
+    }
+
+    let first = proof_with_nonce(&group, &x, &r, b"first message");
+    let second = proof_with_nonce(&group, &x, &r, b"second message");
+    assert_eq!(detector.check_proof(&group, &y, &first), Ok(()));
+    let Err(DetectorError::NonceReuse(err)) = detector.check_proof(&group, &y, &second) else {
+        panic!("reuse not flagged");
+    };
+    assert_eq!(err.public_key, group.encode_element(&y));
+    assert_eq!(
+        err.commitment,
+        group.encode_element(&group.exp(&group.generator(), &r))
+    );
+
+    // The same commitment under another public key is not a reuse
+    let other = group.exp(&group.generator(), &r);
+    assert_eq!(detector.check_proof(&group, &other, &first), Ok(()));
+}
+
+#[test]
+fn detector_forgets_the_oldest_commitments() {
+    let group = PublicParams::new();
+    let y = BigUint::from(9u32);
+    let mut detector = NonceReuseDetector::new(2).unwrap();
+    for t in [2u32, 3, 4] {
+        assert_eq!(detector.check(&group, &y, &BigUint::from(t)), Ok(()));
+    }
+    // 2 was evicted, 4 is still remembered
+    assert_eq!(detector.check(&group, &y, &BigUint::from(2u32)), Ok(()));
+    assert!(detector.check(&group, &y, &BigUint::from(4u32)).is_err());
+}
+
+#[test]
+fn detector_rejects_invalid_elements() {
+    let group = PublicParams::new();
+    let (y, t) = (BigUint::from(9u32), BigUint::from(2u32));
+    let mut detector = NonceReuseDetector::new(4).unwrap();
+    // 5 is outside the subgroup and 2^8 does not even fit an encoding
+    for bad in [BigUint::from(5u32), BigUint::from(256u32)] {
+        assert!(matches!(
+            detector.check(&group, &bad, &t),
+            Err(DetectorError::InvalidPublicKey(_))
+        ));
+        assert!(matches!(
+            detector.check(&group, &y, &bad),
+            Err(DetectorError::InvalidCommitment(_))
+        ));
+    }
+    assert_eq!(
+        detector.check(&group, &y, &BigUint::from(0u32)),
+        Err(DetectorError::InvalidCommitment(ElementError::OutOfRange))
+    );
+    // Rejected commitments are not recorded
+    assert_eq!(detector.check(&group, &y, &t), Ok(()));
+}
+
+#[test]
+fn detector_rejects_zero_capacity() {
+    assert_eq!(NonceReuseDetector::new(0).err(), Some(ZeroCapacityError));
+    let path = std::env::temp_dir().join("zkp-detector-zero-capacity");
+    assert_eq!(
+        NonceReuseDetector::load(&path, 0).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn detector_state_survives_a_restart() {
+    let group = PublicParams::new();
+    let path = std::env::temp_dir().join(format!("zkp-detector-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut detector = NonceReuseDetector::load(&path, 8).unwrap();
+    for (y, t) in [(9u32, 2u32), (9, 3), (13, 2)] {
+        let (y, t) = (BigUint::from(y), BigUint::from(t));
+        assert_eq!(detector.check(&group, &y, &t), Ok(()));
+    }
+    detector.save(&path).unwrap();
+
+    let mut restored = NonceReuseDetector::load(&path, 8).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    for (y, t) in [(9u32, 2u32), (9, 3), (13, 2)] {
+        let (y, t) = (BigUint::from(y), BigUint::from(t));
+        assert!(restored.check(&group, &y, &t).is_err());
+    }
+    assert_eq!(
+        restored.check(&group, &BigUint::from(13u32), &BigUint::from(3u32)),
+        Ok(())
+    );
+}
+
+#[test]
+fn detector_rejects_truncated_files() {
+    let path = std::env::temp_dir().join(format!("zkp-detector-bad-{}", std::process::id()));
+    std::fs::write(&path, [0, 0, 0, 0, 0, 0, 0, 5, 1, 2]).unwrap();
+    let err = NonceReuseDetector::load(&path, 8).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
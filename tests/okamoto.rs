@@ -0,0 +1,182 @@
+mod common;
+
+use num_bigint::BigUint;
+use zkp::group::Ristretto255;
+use zkp::{
+    DecodeError, ElementError, Group, OkamotoProof, OkamotoProver, OkamotoVerifier, ProverError,
+    PublicParams, VerifyError,
+};
+
+use common::{check_proof, check_session, random_elements, random_scalars};
+
+fn round_trip<G: Group>(group: G, bases: Vec<G::Element>) {
+    let secrets = random_scalars(&group, bases.len());
+    let prover = OkamotoProver::new(group.clone(), bases.clone(), secrets).unwrap();
+    let verifier = OkamotoVerifier::new(group.clone(), bases).unwrap();
+    let y = prover.public_key();
+
+    check_proof(
+        &prover.prove(b"context"),
+        |proof, context| verifier.verify_proof(y, proof, context),
+        |proof| proof.to_bytes(&group),
+        |bytes| OkamotoProof::from_bytes(&group, bytes),
+    );
+    check_session(&prover, &verifier, std::slice::from_ref(y));
+}
+
+#[test]
+fn round_trip_over_derived_generators() {
+    let params = PublicParams::rfc5114_2048_256();
+    for n in [1, 2, 5] {
+        let bases = params.independent_generators(n);
+        round_trip(params.clone(), bases);
+    }
+}
+
+#[test]
+fn round_trip_over_ristretto255() {
+    let group = Ristretto255;
+    round_trip(group, random_elements(&group, 3));
+}
+
+#[test]
+fn derived_generators_are_reproducible_and_distinct() {
+    let params = PublicParams::rfc5114_2048_224();
+    let bases = params.independent_generators(8);
+    assert_eq!(params.independent_generators(8), bases);
+    assert_eq!(params.independent_generators(3), bases[..3]);
+
+    for (i, base) in bases.iter().enumerate() {
+        assert!(base > &BigUint::from(1u32) && base < &params.p);
+        assert_eq!(params.check_element(base), Ok(()));
+        assert_ne!(base, &params.g);
+        assert!(!bases[..i].contains(base));
+    }
+
+    // Other parameters give other generators
+    let other = PublicParams::rfc5114_2048_256().independent_generators(8);
+    assert!(bases.iter().all(|b| !other.contains(b)));
+}
+
+#[test]
+fn rejects_mismatched_and_trivial_representations() {
+    let params = PublicParams::new();
+    let bases = params.independent_generators(2);
+    assert!(matches!(
+        OkamotoProver::new(params.clone(), bases.clone(), vec![BigUint::from(1u32)]),
+        Err(ProverError::LengthMismatch {
+            bases: 2,
+            secrets: 1
+        })
+    ));
+    assert!(matches!(
+        OkamotoProver::new(
+            params.clone(),
+            bases.clone(),
+            vec![BigUint::from(0u32), BigUint::from(11u32)]
+        ),
+        Err(ProverError::ZeroSecret)
+    ));
+    // A single zero secret is a valid representation
+    assert!(OkamotoProver::new(
+        params,
+        bases,
+        vec![BigUint::from(0u32), BigUint::from(3u32)]
+    )
+    .is_ok());
+}
+
+#[test]
+fn rejects_invalid_bases() {
+    let params = PublicParams::new();
+    let x = BigUint::from(3u32);
+    // 22 = -1 mod 23 has order 2, 23 = p is out of range, and 1 is the
+    // identity
+    for (base, error) in [
+        (22u32, ElementError::NotInSubgroup),
+        (23, ElementError::OutOfRange),
+        (1, ElementError::Identity),
+    ] {
+        let bases = vec![BigUint::from(9u32), BigUint::from(base)];
+        assert_eq!(
+            OkamotoProver::new(params.clone(), bases.clone(), vec![x.clone(), x.clone()]).err(),
+            Some(ProverError::InvalidBase(error))
+        );
+        assert_eq!(
+            OkamotoVerifier::new(params.clone(), bases).err(),
+            Some(VerifyError::InvalidBase(error))
+        );
+    }
+}
+
+#[test]
+fn rejects_empty_bases() {
+    let params = PublicParams::new();
+    assert_eq!(
+        OkamotoProver::new(params.clone(), vec![], vec![]).err(),
+        Some(ProverError::NoBases)
+    );
+    assert_eq!(
+        OkamotoVerifier::new(params, vec![]).err(),
+        Some(VerifyError::NoBases)
+    );
+}
+
+#[test]
+fn rejects_duplicated_bases() {
+    let params = PublicParams::new();
+    // With g1 = g2, (x1, x2) and (x1 + 1, x2 - 1) represent the same y
+    let bases = vec![
+        BigUint::from(9u32),
+        BigUint::from(4u32),
+        BigUint::from(9u32),
+    ];
+    let secrets = vec![BigUint::from(1u32); 3];
+    assert_eq!(
+        OkamotoProver::new(params.clone(), bases.clone(), secrets).err(),
+        Some(ProverError::DuplicateBase { index: 2 })
+    );
+    assert_eq!(
+        OkamotoVerifier::new(params, bases).err(),
+        Some(VerifyError::DuplicateBase { index: 2 })
+    );
+}
+
+#[test]
+fn rejects_missing_and_unreduced_responses() {
+    let params = PublicParams::new();
+    let bases = vec![BigUint::from(9u32), BigUint::from(13u32)];
+    let secrets = vec![BigUint::from(2u32), BigUint::from(5u32)];
+    let prover = OkamotoProver::new(params.clone(), bases.clone(), secrets).unwrap();
+    let verifier = OkamotoVerifier::new(params.clone(), bases).unwrap();
+    let y = prover.public_key();
+    let proof = prover.prove(b"context");
+
+    let mut short = proof.clone();
+    short.s.pop();
+    assert_eq!(
+        verifier.verify_proof(y, &short, b"context"),
+        Err(VerifyError::ResponseCount {
+            expected: 2,
+            found: 1
+        })
+    );
+    let mut unreduced = proof;
+    unreduced.s[1] += &params.q;
+    assert_eq!(
+        verifier.verify_proof(y, &unreduced, b"context"),
+        Err(VerifyError::NonCanonicalScalar)
+    );
+}
+
+#[test]
+fn rejects_truncated_proofs() {
+    let group = Ristretto255;
+    assert_eq!(
+        OkamotoProof::from_bytes(&group, &[1; 40]),
+        Err(DecodeError::WrongLength {
+            expected: 64,
+            actual: 39
+        })
+    );
+}
@@ -0,0 +1,206 @@
+mod common;
+
+use num_bigint::BigUint;
+use zkp::group::Ristretto255;
+use zkp::{
+    DecodeError, Group, HashAlgorithm, OrProof, OrProver, OrVerifier, ProverError, PublicParams,
+    VerifyError,
+};
+
+use common::{check_proof, check_session, public_keys, random_scalars};
+
+fn round_trip<G: Group>(group: G, n: usize) {
+    let secrets = random_scalars(&group, n);
+    let keys = public_keys(&group, &secrets);
+    let verifier = OrVerifier::new(group.clone(), keys.clone());
+
+    for (index, secret) in secrets.into_iter().enumerate() {
+        let prover = OrProver::new(group.clone(), keys.clone(), index, secret).unwrap();
+        check_proof(
+            &prover.prove(b"context"),
+            |proof, context| verifier.verify_proof(proof, context),
+            |proof| proof.to_bytes(&group),
+            |bytes| OrProof::from_bytes(&group, bytes),
+        );
+        check_session(&prover, &verifier, &[]);
+    }
+}
+
+#[test]
+fn round_trip_over_rfc5114_group() {
+    for n in [1, 2, 4] {
+        round_trip(PublicParams::rfc5114_2048_256(), n);
+    }
+}
+
+#[test]
+fn round_trip_over_ristretto255() {
+    round_trip(Ristretto255, 5);
+}
+
+#[test]
+fn rejects_tampered_branch_challenges() {
+    let group = Ristretto255;
+    let mut secrets = random_scalars(&group, 3);
+    let keys = public_keys(&group, &secrets);
+    let prover = OrProver::new(group, keys.clone(), 2, secrets.pop().unwrap()).unwrap();
+    let verifier = OrVerifier::new(group, keys);
+
+    // Shifting challenge between branches keeps the sum but breaks the branches
+    let one = group.scalar_from_biguint(&BigUint::from(1u32));
+    let mut proof = prover.prove(b"context");
+    proof.c[0] = group.scalar_add(&proof.c[0], &one);
+    proof.c[1] = group.scalar_add(&proof.c[1], &group.scalar_neg(&one));
+    assert_eq!(
+        verifier.verify_proof(&proof, b"context"),
+        Err(VerifyError::EquationFailed)
+    );
+
+    let committed = prover.commit();
+    let challenged = verifier
+        .receive_commitment(committed.commitments())
+        .unwrap()
+        .challenge();
+    let mut s = committed
+        .receive_challenge(*challenged.challenge())
+        .respond()
+        .responses()
+        .to_vec();
+    s[0] = group.scalar_add(&s[0], &one);
+    assert_eq!(
+        challenged.receive_response(s),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+#[test]
+fn rejects_bad_provers() {
+    let params = PublicParams::new();
+    let secrets = random_scalars(&params, 2);
+    let keys = public_keys(&params, &secrets);
+    assert!(matches!(
+        OrProver::new(params.clone(), keys.clone(), 2, secrets[0].clone()),
+        Err(ProverError::IndexOutOfRange { index: 2, len: 2 })
+    ));
+    assert!(matches!(
+        OrProver::new(
+            params.clone(),
+            vec![keys[0].clone()],
+            0,
+            BigUint::from(0u32)
+        ),
+        Err(ProverError::ZeroSecret)
+    ));
+    if keys[0] != keys[1] {
+        assert!(matches!(
+            OrProver::new(params, keys, 1, secrets[0].clone()),
+            Err(ProverError::WrongSecret)
+        ));
+    }
+}
+
+#[test]
+fn rejects_empty_key_lists() {
+    let group = Ristretto255;
+    let secret = random_scalars(&group, 1).remove(0);
+    assert!(matches!(
+        OrProver::new(group, vec![], 0, secret),
+        Err(ProverError::IndexOutOfRange { index: 0, len: 0 })
+    ));
+    // With no branches, the branch challenges cannot sum to c
+    let verifier = OrVerifier::new(group, vec![]);
+    let proof = OrProof {
+        c: vec![],
+        s: vec![],
+        hash: HashAlgorithm::Sha256,
+    };
+    assert_eq!(
+        verifier.verify_proof(&proof, b"context"),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+#[test]
+fn rejects_missing_and_unreduced_responses() {
+    let params = PublicParams::new();
+    // 9 = g^8 in the toy group
+    let keys = vec![BigUint::from(9u32), BigUint::from(13u32)];
+    let prover = OrProver::new(params.clone(), keys.clone(), 0, BigUint::from(8u32)).unwrap();
+    let verifier = OrVerifier::new(params.clone(), keys);
+    let proof = prover.prove(b"context");
+
+    let mut short = proof.clone();
+    short.s.pop();
+    assert_eq!(
+        verifier.verify_proof(&short, b"context"),
+        Err(VerifyError::ResponseCount {
+            expected: 2,
+            found: 1
+        })
+    );
+    for unreduced in [
+        OrProof {
+            c: vec![&proof.c[0] + &params.q, proof.c[1].clone()],
+            ..proof.clone()
+        },
+        OrProof {
+            s: vec![proof.s[0].clone(), &proof.s[1] + &params.q],
+            ..proof.clone()
+        },
+    ] {
+        assert_eq!(
+            verifier.verify_proof(&unreduced, b"context"),
+            Err(VerifyError::NonCanonicalScalar)
+        );
+    }
+}
+
+#[test]
+fn branch_challenges_do_not_reveal_the_known_branch() {
+    // Over the toy group every branch challenge should be uniform on [0, 11),
+    // whichever branch the prover knows.
+    let params = PublicParams::new();
+    let keys = vec![
+        params.exp(&params.generator(), &BigUint::from(3u32)),
+        params.exp(&params.generator(), &BigUint::from(7u32)),
+    ];
+    for (index, x) in [(0, 3u32), (1, 7u32)] {
+        let prover = OrProver::new(params.clone(), keys.clone(), index, BigUint::from(x)).unwrap();
+        let mut counts = [[0u32; 11]; 2];
+        for i in 0u32..11000 {
+            let proof = prover.prove(&i.to_be_bytes());
+            for (j, c_j) in proof.c.iter().enumerate() {
+                let c_j: usize = c_j.try_into().unwrap();
+                counts[j][c_j] += 1;
+            }
+        }
+        // Each value is expected 1000 times on each branch
+        for (j, branch) in counts.iter().enumerate() {
+            for (c, &count) in branch.iter().enumerate() {
+                assert!(
+                    (850..1150).contains(&count),
+                    "index {index}: c{j} = {c} produced {count} times"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn rejects_malformed_encodings() {
+    let group = Ristretto255;
+    assert_eq!(
+        OrProof::from_bytes(&group, &[1]),
+        Err(DecodeError::WrongLength {
+            expected: 64,
+            actual: 0
+        })
+    );
+    assert_eq!(
+        OrProof::from_bytes(&group, &[1; 97]),
+        Err(DecodeError::WrongLength {
+            expected: 128,
+            actual: 96
+        })
+    );
+}
@@ -0,0 +1,106 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use zkp::{ParamsError, PublicParams, Verifier};
+
+#[test]
+fn generates_safe_prime_params_that_validate() {
+    let mut rng = rand::thread_rng();
+    for q_bits in [2, 16, 64] {
+        let params = PublicParams::generate(q_bits + 1, q_bits, &mut rng);
+        assert_eq!(params.p.bits(), q_bits + 1);
+        assert_eq!(params.q.bits(), q_bits);
+        assert_eq!(params.p, &params.q * 2u32 + 1u32);
+        assert_eq!(params.provenance, None);
+        assert_eq!(params.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn generates_dsa_style_params_that_validate() {
+    let mut rng = rand::thread_rng();
+    for (p_bits, q_bits) in [(64, 16), (256, 64), (512, 160)] {
+        let params = PublicParams::generate(p_bits, q_bits, &mut rng);
+        assert_eq!(params.p.bits(), p_bits);
+        assert_eq!(params.q.bits(), q_bits);
+        assert!(((&params.p - 1u32) % &params.q).is_zero());
+        assert!(params.g > BigUint::one() && params.g < params.p);
+        assert!(params.g.modpow(&params.q, &params.p).is_one());
+        assert_eq!(params.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn generated_params_differ_between_runs() {
+    let mut rng = rand::thread_rng();
+    let first = PublicParams::generate(128, 32, &mut rng);
+    let second = PublicParams::generate(128, 32, &mut rng);
+    assert_ne!(first, second);
+}
+
+#[test]
+#[should_panic(expected = "q must have at least 2 bits")]
+fn generate_rejects_one_bit_q() {
+    PublicParams::generate(8, 1, &mut rand::thread_rng());
+}
+
+#[test]
+#[should_panic(expected = "q must be shorter than p")]
+fn generate_rejects_q_as_long_as_p() {
+    PublicParams::generate(16, 16, &mut rand::thread_rng());
+}
+
+#[test]
+#[should_panic(expected = "q must be shorter than p")]
+fn generate_rejects_q_longer_than_p() {
+    PublicParams::generate(16, 32, &mut rand::thread_rng());
+}
+
+fn params(p: u32, q: u32, g: u32) -> PublicParams {
+    PublicParams {
+        p: BigUint::from(p),
+        q: BigUint::from(q),
+        g: BigUint::from(g),
+        provenance: None,
+    }
+}
+
+/// Parameters that break exactly one of the checks in `validate`.
+fn invalid_params() -> Vec<(PublicParams, ParamsError)> {
+    vec![
+        // g must lie in (1, p)
+        (params(23, 11, 0), ParamsError::GeneratorOutOfRange),
+        (params(23, 11, 1), ParamsError::GeneratorOutOfRange),
+        (params(23, 11, 23), ParamsError::GeneratorOutOfRange),
+        (params(23, 11, 27), ParamsError::GeneratorOutOfRange),
+        // q must divide p-1 = 22
+        (params(23, 7, 4), ParamsError::OrderDoesNotDivide),
+        (params(23, 0, 4), ParamsError::OrderDoesNotDivide),
+        // q = 22 divides p-1 but is composite
+        (params(23, 22, 4), ParamsError::OrderNotPrime),
+        // p = 25 and the Carmichael number 561 are composite
+        (params(25, 3, 2), ParamsError::ModulusNotPrime),
+        (params(561, 7, 2), ParamsError::ModulusNotPrime),
+        // 5 generates all of Z_23*, and 22 = -1 has order 2
+        (params(23, 11, 5), ParamsError::GeneratorWrongOrder),
+        (params(23, 11, 22), ParamsError::GeneratorWrongOrder),
+    ]
+}
+
+#[test]
+fn validate_reports_each_broken_check() {
+    for (params, error) in invalid_params() {
+        assert_eq!(params.validate(), Err(error), "{params:?}");
+        assert_eq!(params.validate_with_rounds(16), Err(error), "{params:?}");
+    }
+    assert_eq!(PublicParams::new().validate(), Ok(()));
+    assert_eq!(PublicParams::rfc5114_2048_256().validate(), Ok(()));
+}
+
+#[test]
+fn new_checked_rejects_invalid_params() {
+    for (params, error) in invalid_params() {
+        assert_eq!(Verifier::new_checked(params).err(), Some(error));
+    }
+    assert!(Verifier::new_checked(PublicParams::new()).is_ok());
+    assert!(Verifier::new_checked(PublicParams::rfc5114_2048_256()).is_ok());
+}
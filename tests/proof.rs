@@ -0,0 +1,103 @@
+mod common;
+
+use num_bigint::BigUint;
+use sha2::Sha512;
+use zkp::group::{Group, Ristretto255, Secp256k1};
+use zkp::{DecodeError, HashAlgorithm, Prover, PublicParams, SchnorrProof, Verifier, VerifyError};
+
+use common::{check_proof, check_session, random_elements, random_prover};
+
+const CONTEXT: &[u8] = b"proof tests";
+
+fn round_trip<G: Group>(group: G) {
+    let prover = random_prover(&group);
+    let verifier = Verifier::new(group.clone());
+    let y = prover.public_key();
+
+    let proof = prover.prove(b"context");
+    check_proof(
+        &proof,
+        |proof, context| verifier.verify_proof(y, proof, context),
+        |proof| proof.to_bytes(&group),
+        |bytes| SchnorrProof::from_bytes(&group, bytes),
+    );
+    check_session(&prover, &verifier, std::slice::from_ref(y));
+    assert_eq!(proof.to_bytes(&group)[0], HashAlgorithm::Sha256.id());
+
+    // A proof does not carry over to another public key
+    let other = random_elements(&group, 1).remove(0);
+    assert_eq!(
+        verifier.verify_proof(&other, &proof, b"context"),
+        Err(VerifyError::EquationFailed)
+    );
+
+    // Tampering with the response is detected
+    let mut tampered = proof.clone();
+    tampered.s = group.scalar_add(
+        &tampered.s,
+        &group.scalar_from_biguint(&BigUint::from(1u32)),
+    );
+    assert_eq!(
+        verifier.verify_proof(y, &tampered, b"context"),
+        Err(VerifyError::EquationFailed)
+    );
+}
+
+#[test]
+fn proofs_round_trip() {
+    round_trip(PublicParams::rfc5114_2048_256());
+    round_trip(Ristretto255);
+    round_trip(Secp256k1);
+}
+
+#[test]
+fn proofs_record_the_hash_function() {
+    let group = Ristretto255;
+    let secret = group.random_nonzero_scalar(&mut rand::thread_rng());
+    let prover = Prover::<_, Sha512>::with_hash(group, secret).unwrap();
+    let proof = prover.prove(CONTEXT);
+    assert_eq!(proof.hash, HashAlgorithm::Sha512);
+
+    let verifier = Verifier::<_, Sha512>::with_hash(group);
+    assert_eq!(
+        verifier.verify_proof(prover.public_key(), &proof, CONTEXT),
+        Ok(())
+    );
+
+    // A SHA-256 verifier refuses the proof instead of checking it
+    assert_eq!(
+        Verifier::new(group).verify_proof(prover.public_key(), &proof, CONTEXT),
+        Err(VerifyError::HashMismatch {
+            expected: HashAlgorithm::Sha256,
+            found: HashAlgorithm::Sha512,
+        })
+    );
+
+    let mut bytes = proof.to_bytes(&group);
+    bytes[0] = 0xff;
+    assert_eq!(
+        SchnorrProof::from_bytes(&group, &bytes),
+        Err(DecodeError::UnknownHash(0xff))
+    );
+}
+
+#[test]
+fn rejects_proofs_of_the_wrong_length() {
+    let group = Ristretto255;
+    for len in [0, 1, 32, 64, 66, 97] {
+        let mut bytes = vec![0; len];
+        if let Some(id) = bytes.first_mut() {
+            *id = HashAlgorithm::Sha256.id();
+        }
+        let expected = if len == 0 { 1 } else { 64 };
+        assert_eq!(
+            SchnorrProof::from_bytes(&group, &bytes),
+            Err(DecodeError::WrongLength {
+                expected,
+                actual: len.saturating_sub(1)
+            }),
+            "{len} bytes"
+        );
+    }
+    assert!(SchnorrProof::from_bytes(&group, &[HashAlgorithm::Sha256.id(); 65]).is_ok());
+}
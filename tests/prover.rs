@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use num_bigint::BigUint;
+use zkp::{Prover, ProverError, PublicParams};
+
+#[test]
+fn rejects_secrets_that_are_multiples_of_q() {
+    for secret in [0u32, 11, 22, 110] {
+        assert!(matches!(
+            Prover::new(PublicParams::new(), BigUint::from(secret)),
+            Err(ProverError::ZeroSecret)
+        ));
+    }
+    assert!(Prover::new(PublicParams::new(), BigUint::from(12u32)).is_ok());
+}
+
+#[test]
+fn nonces_are_drawn_from_one_to_q_minus_one() {
+    let params = PublicParams::new();
+    let prover = Prover::new(params.clone(), BigUint::from(6u32)).unwrap();
+    // The nonce stays inside the session, so recover it from t = g^r
+    let log: HashMap<BigUint, usize> = (0..11u32)
+        .map(|r| (params.g.modpow(&BigUint::from(r), &params.p), r as usize))
+        .collect();
+    let mut counts = [0u32; 11];
+    for _ in 0..2000 {
+        let committed = prover.commit();
+        let r = log[&committed.commitments()[0]];
+        assert!(r != 0, "commit drew r = 0");
+        counts[r] += 1;
+    }
+    // Each of the 10 nonzero values is expected 200 times
+    for (r, &count) in counts.iter().enumerate().skip(1) {
+        assert!((100..300).contains(&count), "r = {r} drawn {count} times");
+    }
+}
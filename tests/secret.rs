@@ -0,0 +1,39 @@
+use curve25519_dalek::Scalar;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use zkp::secret::Zeroizable;
+use zkp::Secret;
+
+#[test]
+fn debug_output_is_redacted() {
+    let secret = Secret::new(BigUint::from(0xdead_beef_u32));
+    let debug = format!("{secret:?}");
+    assert_eq!(debug, "Secret([REDACTED])");
+    assert!(!debug.contains("3735928559"));
+}
+
+#[test]
+fn expose_secret_returns_the_value() {
+    let secret = Secret::new(BigUint::from(42u32));
+    assert_eq!(secret.expose_secret(), &BigUint::from(42u32));
+}
+
+#[test]
+fn zeroize_clears_big_integers() {
+    for bits in [1u32, 63, 64, 65, 2048] {
+        let mut value = (BigUint::one() << bits) - 1u32;
+        value.zeroize();
+        assert!(value.is_zero(), "{bits}-bit value not cleared");
+    }
+}
+
+#[test]
+fn zeroize_clears_curve_scalars() {
+    let mut scalar = Scalar::from(42u64);
+    Zeroizable::zeroize(&mut scalar);
+    assert_eq!(scalar, Scalar::ZERO);
+
+    let mut scalar = k256::Scalar::from(42u64);
+    Zeroizable::zeroize(&mut scalar);
+    assert_eq!(scalar, k256::Scalar::ZERO);
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use num_bigint::{BigUint, RandBigInt};
+use zkp::simulator::simulate;
+use zkp::{Group, Prover, PublicParams, Verifier};
+
+/// Transcripts per distribution; each of the 110 possible (t, c) pairs is
+/// expected 500 times.
+const SAMPLES: usize = 55_000;
+
+/// 99.9th percentile of the chi-squared distribution with 109 degrees of
+/// freedom.
+const CHI_SQUARED_CRITICAL: f64 = 159.0;
+
+#[test]
+fn simulated_transcripts_are_accepted() {
+    let group = PublicParams::new();
+    let prover = Prover::new(group.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(group.clone());
+    for c in 0u32..11 {
+        let (t, c, s) = simulate(&group, prover.public_key(), &BigUint::from(c));
+        assert_eq!(verifier.verify(&t, &c, &s, prover.public_key()), Ok(()));
+    }
+}
+
+#[test]
+fn simulated_and_real_transcripts_have_the_same_distribution() {
+    let group = PublicParams::new();
+    let prover = Prover::new(group.clone(), BigUint::from(6u32)).unwrap();
+    let verifier = Verifier::new(group.clone());
+    let y = prover.public_key();
+    let mut rng = rand::thread_rng();
+
+    // s is determined by t and c in an accepting transcript, so counting
+    // (t, c) pairs captures the whole distribution
+    let mut real: HashMap<(BigUint, BigUint), usize> = HashMap::new();
+    let mut simulated: HashMap<(BigUint, BigUint), usize> = HashMap::new();
+    for _ in 0..SAMPLES {
+        let committed = prover.commit();
+        let challenged = verifier
+            .receive_commitment(y, &committed.commitments()[0])
+            .unwrap()
+            .challenge();
+        let responded = committed
+            .receive_challenge(challenged.challenge().clone())
+            .respond();
+        let key = (
+            responded.commitments()[0].clone(),
+            responded.challenge().clone(),
+        );
+        *real.entry(key).or_default() += 1;
+
+        let c = rng.gen_biguint_below(&group.order());
+        let (t, c, s) = simulate(&group, y, &c);
+        assert_eq!(verifier.verify(&t, &c, &s, y), Ok(()));
+        *simulated.entry((t, c)).or_default() += 1;
+    }
+
+    // Both distributions are uniform over t != 1 and all c
+    assert_eq!(real.len(), 110);
+    assert_eq!(simulated.len(), 110);
+
+    // Two-sample chi-squared test of homogeneity
+    let chi_squared: f64 = real
+        .iter()
+        .map(|(key, &a)| {
+            let b = simulated.get(key).copied().unwrap_or(0);
+            let (a, b) = (a as f64, b as f64);
+            (a - b).powi(2) / (a + b)
+        })
+        .sum();
+    assert!(
+        chi_squared < CHI_SQUARED_CRITICAL,
+        "chi-squared = {chi_squared:.1}"
+    );
+}
@@ -0,0 +1,188 @@
+//! Constant-time exponentiation for secret exponents.
+//!
+//! The timing tests are statistical and slow, so they are ignored by default.
+//! Run them in release mode on a quiet machine:
+//!
+//! ```text
+//! cargo test --release --test timing -- --ignored --nocapture
+//! ```
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::Rng;
+use zkp::{DleqProver, Group, OkamotoProver, Prover, ProverError, PublicParams};
+
+/// Welch's t statistic above which dudect reports a timing leak.
+const T_THRESHOLD: f64 = 4.5;
+
+/// Number of timed exponentiations per class.
+const SAMPLES: usize = 20_000;
+
+#[test]
+fn exp_secret_agrees_with_exp() {
+    let mut rng = rand::thread_rng();
+    for params in [
+        PublicParams::new(),
+        PublicParams::rfc5114_1024_160(),
+        PublicParams::rfc5114_2048_256(),
+        PublicParams::modp_3072(),
+    ] {
+        let g = params.generator();
+        for k in [
+            BigUint::from(0u32),
+            BigUint::from(1u32),
+            &params.q - 1u32,
+            // Unreduced representatives are reduced first
+            &params.q + 5u32,
+            rng.gen_biguint_below(&params.q),
+        ] {
+            assert_eq!(params.exp_secret(&g, &k), params.exp(&g, &k));
+        }
+    }
+}
+
+#[test]
+fn exp_secret_reduces_every_exponent() {
+    let mut rng = rand::thread_rng();
+    for params in [PublicParams::new(), PublicParams::rfc5114_2048_256()] {
+        let g = params.generator();
+        let k = rng.gen_biguint_below(&params.q);
+        let expected = params.exp_secret(&g, &k);
+        // q, 2q and a multiple far wider than q are all congruent to 0
+        for multiple in [1u32, 2, 1 << 20] {
+            let unreduced = &k + &params.q * multiple;
+            assert_eq!(params.exp_secret(&g, &unreduced), expected);
+        }
+        assert_eq!(
+            params.exp_secret(&g, &(&params.q * 3u32)),
+            params.identity()
+        );
+        // Exponents wider than p, or than the widest fixed-width integer, and
+        // bases above p do not panic
+        for bits in [params.p.bits() + 64, 20_000] {
+            let wide = &k + (&params.q << bits);
+            assert_eq!(params.exp_secret(&g, &wide), expected);
+        }
+        assert_eq!(params.exp_secret(&(&g + &params.p), &k), expected);
+    }
+}
+
+/// Measures `exp` on the generator with exponents from two classes, in a
+/// random interleaving, and returns Welch's t statistic between them.
+///
+/// The first class always uses the exponent 1 and the second a fresh random
+/// exponent, as in dudect's fixed-versus-random test.
+fn fixed_vs_random_t(params: &PublicParams, exp: impl Fn(&BigUint) -> BigUint) -> f64 {
+    let mut rng = rand::thread_rng();
+    let fixed = BigUint::from(1u32);
+    let mut timings = [Vec::with_capacity(SAMPLES), Vec::with_capacity(SAMPLES)];
+    while timings.iter().any(|t| t.len() < SAMPLES) {
+        let class = rng.gen_range(0..2);
+        let k = if class == 0 {
+            fixed.clone()
+        } else {
+            rng.gen_biguint_below(&params.q)
+        };
+        let start = Instant::now();
+        black_box(exp(black_box(&k)));
+        timings[class].push(start.elapsed().as_nanos() as f64);
+    }
+
+    // Drop the slowest measurements, which are dominated by interrupts and
+    // scheduling rather than by the computation
+    let mut pooled: Vec<f64> = timings.iter().flatten().copied().collect();
+    pooled.sort_by(f64::total_cmp);
+    let cutoff = pooled[pooled.len() * 9 / 10];
+    let [a, b] = timings.map(|t| t.into_iter().filter(|&x| x <= cutoff).collect::<Vec<_>>());
+    welch_t(&a, &b)
+}
+
+/// Returns Welch's t statistic for the difference in means of `a` and `b`.
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let stats = |xs: &[f64]| {
+        let n = xs.len() as f64;
+        let mean = xs.iter().sum::<f64>() / n;
+        let var = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (n, mean, var)
+    };
+    let (na, ma, va) = stats(a);
+    let (nb, mb, vb) = stats(b);
+    (ma - mb) / (va / na + vb / nb).sqrt()
+}
+
+#[test]
+#[ignore = "statistical timing test; run with --release -- --ignored"]
+fn exp_secret_timing_does_not_depend_on_the_exponent() {
+    let params = PublicParams::rfc5114_2048_256();
+    let g = params.generator();
+
+    let t = fixed_vs_random_t(&params, |k| params.exp_secret(&g, k));
+    println!("exp_secret: t = {t:.2}");
+    assert!(t.abs() < T_THRESHOLD, "exp_secret leaks timing: t = {t:.2}");
+}
+
+#[test]
+#[ignore = "statistical timing test; run with --release -- --ignored"]
+fn harness_detects_variable_time_exp() {
+    let params = PublicParams::rfc5114_2048_256();
+    let g = params.generator();
+
+    // num-bigint's modpow returns almost immediately for the exponent 1
+    let t = fixed_vs_random_t(&params, |k| params.exp(&g, k));
+    println!("exp: t = {t:.2}");
+    assert!(
+        t.abs() > T_THRESHOLD,
+        "harness missed a timing leak: t = {t:.2}"
+    );
+}
+
+/// Parameters that `exp_secret` cannot handle in constant time: an even
+/// modulus, and one wider than the widest fixed-width integer.
+fn unsupported_params() -> Vec<PublicParams> {
+    let params = |p: BigUint, q: u32| PublicParams {
+        p,
+        q: BigUint::from(q),
+        g: BigUint::from(2u32),
+        provenance: None,
+    };
+    vec![
+        params(BigUint::from(24u32), 11),
+        params((BigUint::one() << 16385) + 1u32, 3),
+    ]
+}
+
+#[test]
+fn exp_secret_falls_back_for_unsupported_params() {
+    for params in unsupported_params() {
+        assert!(!params.supports_exp_secret());
+        let g = params.generator();
+        for k in [0u32, 1, 2, 5] {
+            let k = BigUint::from(k);
+            assert_eq!(params.exp_secret(&g, &k), params.exp(&g, &k));
+        }
+    }
+    assert!(PublicParams::new().supports_exp_secret());
+    assert!(PublicParams::modp_3072().supports_exp_secret());
+}
+
+#[test]
+fn provers_reject_unsupported_params() {
+    for params in unsupported_params() {
+        let x = BigUint::from(2u32);
+        assert_eq!(
+            Prover::new(params.clone(), x.clone()).err(),
+            Some(ProverError::UnsupportedGroup)
+        );
+        assert_eq!(
+            DleqProver::new(params.clone(), params.generator(), x.clone()).err(),
+            Some(ProverError::UnsupportedGroup)
+        );
+        assert_eq!(
+            OkamotoProver::new(params.clone(), vec![params.generator()], vec![x]).err(),
+            Some(ProverError::UnsupportedGroup)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use zkp::group::{Group, Secp256k1, P256};
+use zkp::{DecodeError, ElementError, Prover, Verifier};
+
+fn decode_hex(s: &str) -> Vec<u8> {
+    hex::decode(s).expect("test vectors are valid hex")
+}
+
+/// Checks encoding, decoding and a Schnorr run over `group`.
+fn check_group<G: Group>(group: G, generator: &str, off_curve: &str) {
+    let g = group.encode_element(&group.generator());
+    assert_eq!(hex::encode_upper(&g), generator);
+    assert_eq!(group.decode_element(&g), Ok(group.generator()));
+
+    // Off-curve points, the point at infinity and uncompressed points are rejected
+    assert_eq!(
+        group.decode_element(&decode_hex(off_curve)),
+        Err(DecodeError::NotOnCurve)
+    );
+    assert_eq!(group.decode_element(&[0x00]), Err(DecodeError::Identity));
+    assert_eq!(
+        group.check_element(&group.identity()),
+        Err(ElementError::Identity)
+    );
+    let mut bad_tag = g.clone();
+    bad_tag[0] = 0x05;
+    assert_eq!(
+        group.decode_element(&bad_tag),
+        Err(DecodeError::InvalidPoint)
+    );
+    assert!(matches!(
+        group.decode_element(&g[..32]),
+        Err(DecodeError::WrongLength { .. })
+    ));
+
+    let mut rng = rand::thread_rng();
+    let s = group.random_scalar(&mut rng);
+    assert_eq!(group.decode_scalar(&group.encode_scalar(&s)), Ok(s.clone()));
+    assert_eq!(group.scalar_from_biguint(&group.scalar_to_biguint(&s)), s);
+    let order = group.order().to_bytes_be();
+    assert_eq!(group.decode_scalar(&order), Err(DecodeError::OutOfRange));
+    for len in [0, 31, 33] {
+        assert_eq!(
+            group.decode_scalar(&vec![0; len]),
+            Err(DecodeError::WrongLength {
+                expected: 32,
+                actual: len
+            })
+        );
+    }
+
+    let prover = Prover::new(group.clone(), group.random_nonzero_scalar(&mut rng)).unwrap();
+    let verifier = Verifier::new(group);
+    let committed = prover.commit();
+    let challenged = verifier
+        .receive_commitment(prover.public_key(), &committed.commitments()[0])
+        .unwrap()
+        .challenge();
+    let responded = committed
+        .receive_challenge(challenged.challenge().clone())
+        .respond();
+    assert!(challenged
+        .receive_response(responded.responses().to_vec())
+        .is_ok());
+}
+
+#[test]
+fn secp256k1() {
+    check_group(
+        Secp256k1,
+        "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        "020000000000000000000000000000000000000000000000000000000000000000",
+    );
+}
+
+#[test]
+fn p256() {
+    check_group(
+        P256,
+        "036B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+        "020000000000000000000000000000000000000000000000000000000000000001",
+    );
+}